@@ -1,6 +1,5 @@
-use tilejson::decode;
+use tilejson::TileJson;
 
-use std::fs;
 use std::env;
 
 fn main() {
@@ -9,8 +8,7 @@ fn main() {
         println!("Provide file with json as parameter.");
         return;
     }
-    let json = fs::read_to_string(&args[1]).unwrap();
 
-    let tile = decode(&json);
-    println!("{:#?}", tile);
-}
\ No newline at end of file
+    let tile = TileJson::from_file(&args[1]).unwrap();
+    println!("{}", tile);
+}
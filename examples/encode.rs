@@ -1,9 +1,10 @@
-use tilejson::{TileJson, encode};
+use tilejson::TileJson;
 
 fn main() {
-    let mut tilejson = TileJson::default();
-    tilejson.name = Some("TileSet Name".to_owned());
-    tilejson.description = Some("TileSet description".to_owned());
-    let json = encode(&tilejson);
-    println!("{:#?}", json);
-}
\ No newline at end of file
+    let tilejson = TileJson::builder()
+        .name("TileSet Name")
+        .description("TileSet description")
+        .tile("https://tiles.example.com/{z}/{x}/{y}.png")
+        .build();
+    println!("{}", tilejson);
+}
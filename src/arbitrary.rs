@@ -0,0 +1,163 @@
+//! Generators of valid-by-construction `TileJson` documents for fuzzing and
+//! property testing, behind the `arbitrary` and `proptest` features.
+
+use crate::bounds::Bounds;
+use crate::center::Center;
+use crate::tilejson::{Scheme, TileJson};
+
+/// The raw, unconstrained input a `TileJson` is shaped from.
+#[derive(Debug, Clone)]
+struct Seed {
+    spec_v3: bool,
+    name: Option<String>,
+    tms: bool,
+    minzoom: u8,
+    zoom_span: u8,
+    longitudes: (u16, u16),
+    latitudes: (u16, u16),
+    center: Option<(u16, u16, u8)>,
+    subdomains: u8,
+    extension: u8,
+}
+
+const EXTENSIONS: [&str; 4] = ["png", "jpg", "webp", "pbf"];
+
+impl Seed {
+    /// Shapes the seed into a document whose zoom range, bounds, center and
+    /// tile endpoints all satisfy the spec.
+    fn build(self) -> TileJson {
+        let minzoom = self.minzoom % 31;
+        let maxzoom = minzoom + self.zoom_span % (31 - minzoom);
+        let (left, right) = span(self.longitudes, 3600, -180.0);
+        let (bottom, top) = span(self.latitudes, 1800, -90.0);
+
+        let center = self.center.map(|(lon, lat, zoom)| {
+            let lon = left + (right - left) * f64::from(lon) / f64::from(u16::MAX);
+            let lat = bottom + (top - bottom) * f64::from(lat) / f64::from(u16::MAX);
+            let zoom = minzoom + zoom % (maxzoom - minzoom + 1);
+            Center { longitude: lon, latitude: lat, zoom: Some(zoom) }
+        });
+
+        let extension = EXTENSIONS[usize::from(self.extension) % EXTENSIONS.len()];
+        let tiles = (0..=self.subdomains % 3)
+            .map(|i| format!("https://{}.tiles.example.com/{{z}}/{{x}}/{{y}}.{}", (b'a' + i) as char, extension))
+            .collect();
+
+        TileJson {
+            tilejson: if self.spec_v3 { "3.0.0" } else { "2.2.0" }.to_owned(),
+            name: self.name,
+            scheme: if self.tms { Scheme::TMS } else { Scheme::XYZ },
+            tiles,
+            minzoom,
+            maxzoom,
+            bounds: Some(Bounds { left, bottom, right, top }),
+            center,
+            ..TileJson::default()
+        }
+    }
+}
+
+/// Turns two raw values into a non-empty `(min, max)` range of tenths of a
+/// degree within `0..=steps`, offset by `origin` degrees.
+fn span((a, b): (u16, u16), steps: u16, origin: f64) -> (f64, f64) {
+    let low = a % steps;
+    let high = low + 1 + b % (steps - low);
+    (origin + f64::from(low) / 10.0, origin + f64::from(high) / 10.0)
+}
+
+#[cfg(feature = "arbitrary")]
+impl<'a> arbitrary::Arbitrary<'a> for TileJson {
+    fn arbitrary(u: &mut arbitrary::Unstructured<'a>) -> arbitrary::Result<Self> {
+        let seed = Seed {
+            spec_v3: u.arbitrary()?,
+            name: u.arbitrary()?,
+            tms: u.arbitrary()?,
+            minzoom: u.arbitrary()?,
+            zoom_span: u.arbitrary()?,
+            longitudes: u.arbitrary()?,
+            latitudes: u.arbitrary()?,
+            center: u.arbitrary()?,
+            subdomains: u.arbitrary()?,
+            extension: u.arbitrary()?,
+        };
+        Ok(seed.build())
+    }
+}
+
+#[cfg(feature = "proptest")]
+impl proptest::arbitrary::Arbitrary for TileJson {
+    type Parameters = ();
+    type Strategy = proptest::strategy::BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        use proptest::prelude::*;
+
+        let flags = (any::<bool>(), any::<bool>(), any::<u8>(), any::<u8>());
+        let name = proptest::option::of("[a-zA-Z0-9 _-]{0,32}");
+        let extent = (any::<(u16, u16)>(), any::<(u16, u16)>(), any::<Option<(u16, u16, u8)>>());
+        (flags, name, extent, any::<(u8, u8)>())
+            .prop_map(|((spec_v3, tms, minzoom, zoom_span), name, (longitudes, latitudes, center), (subdomains, extension))| {
+                Seed {
+                    spec_v3,
+                    name,
+                    tms,
+                    minzoom,
+                    zoom_span,
+                    longitudes,
+                    latitudes,
+                    center,
+                    subdomains,
+                    extension,
+                }
+                .build()
+            })
+            .boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_seed_extremes_are_valid() {
+        for raw in [0, 1, u8::MAX / 2, u8::MAX] {
+            let wide = u16::from(raw) * 257;
+            let seed = Seed {
+                spec_v3: raw % 2 == 0,
+                name: None,
+                tms: raw % 2 == 1,
+                minzoom: raw,
+                zoom_span: raw,
+                longitudes: (wide, wide),
+                latitudes: (wide, u16::MAX - wide),
+                center: Some((wide, wide, raw)),
+                subdomains: raw,
+                extension: raw,
+            };
+            let tilejson = seed.build();
+            assert_eq!(tilejson.validate(), Ok(()), "{:?}", tilejson);
+            assert!(tilejson.minzoom <= tilejson.maxzoom && tilejson.maxzoom <= 30);
+        }
+    }
+
+    #[cfg(feature = "arbitrary")]
+    #[test]
+    fn test_arbitrary_is_valid() {
+        use arbitrary::{Arbitrary, Unstructured};
+
+        for len in 0..64u8 {
+            let bytes: Vec<u8> = (0..len).map(|i| i.wrapping_mul(97).wrapping_add(len)).collect();
+            let tilejson = TileJson::arbitrary(&mut Unstructured::new(&bytes)).unwrap();
+            assert_eq!(tilejson.validate(), Ok(()), "{:?}", tilejson);
+        }
+    }
+
+    #[cfg(feature = "proptest")]
+    proptest::proptest! {
+        #[test]
+        fn test_proptest_is_valid(tilejson in proptest::prelude::any::<TileJson>()) {
+            proptest::prop_assert_eq!(tilejson.validate(), Ok(()));
+        }
+    }
+}
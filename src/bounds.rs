@@ -0,0 +1,148 @@
+use crate::hash::hash_f64;
+
+#[cfg(feature = "serde")]
+use serde::de::{self, Unexpected, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A `[left, bottom, right, top]` WGS:84 extent. `left > right` means the
+/// extent crosses the antimeridian.
+///
+/// Bounds are `Eq` and `Hash` as long as they hold no NaN: one that does
+/// isn't equal to itself.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Bounds {
+    pub left: f64,
+    pub bottom: f64,
+    pub right: f64,
+    pub top: f64,
+}
+
+impl Eq for Bounds {}
+
+impl Hash for Bounds {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        for coordinate in [self.left, self.bottom, self.right, self.top] {
+            hash_f64(coordinate, state);
+        }
+    }
+}
+
+/// The whole world, `[-180, -90, 180, 90]`.
+pub(crate) const WORLD: Bounds = Bounds { left: -180.0, bottom: -90.0, right: 180.0, top: 90.0 };
+
+impl Default for Bounds {
+    /// The whole world, `[-180, -90, 180, 90]`.
+    fn default() -> Self {
+        WORLD
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Bounds {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        <[f64; 4]>::from(*self).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Bounds {
+    /// Reads the `[left, bottom, right, top]` array form, rejecting arrays
+    /// that don't hold exactly four numbers. Each number may also be given
+    /// as a numeric string, as in `["-180", "-85", "180", "85"]`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let coordinates = <[Coordinate; 4]>::deserialize(deserializer)?;
+        Ok(Bounds::from(coordinates.map(|Coordinate(value)| value)))
+    }
+}
+
+/// A coordinate read from either a JSON number or a numeric string such as
+/// `"-85.0511"`, which some tile servers emit.
+#[cfg(feature = "serde")]
+pub(crate) struct Coordinate(pub(crate) f64);
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Coordinate {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_any(CoordinateVisitor).map(Coordinate)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CoordinateVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for CoordinateVisitor {
+    type Value = f64;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a coordinate as a number or a numeric string")
+    }
+
+    fn visit_f64<E: de::Error>(self, value: f64) -> Result<f64, E> {
+        Ok(value)
+    }
+
+    fn visit_u64<E: de::Error>(self, value: u64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_i64<E: de::Error>(self, value: i64) -> Result<f64, E> {
+        Ok(value as f64)
+    }
+
+    fn visit_str<E: de::Error>(self, value: &str) -> Result<f64, E> {
+        match value.trim().parse::<f64>() {
+            Ok(parsed) if parsed.is_finite() => Ok(parsed),
+            _ => Err(E::invalid_value(Unexpected::Str(value), &self)),
+        }
+    }
+}
+
+impl From<[f64; 4]> for Bounds {
+    fn from([left, bottom, right, top]: [f64; 4]) -> Self {
+        Bounds { left, bottom, right, top }
+    }
+}
+
+impl From<Bounds> for [f64; 4] {
+    fn from(bounds: Bounds) -> Self {
+        [bounds.left, bounds.bottom, bounds.right, bounds.top]
+    }
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let bounds: Bounds = serde_json::from_str("[-180,-85,180,85]").unwrap();
+        assert_eq!(bounds, Bounds { left: -180.0, bottom: -85.0, right: 180.0, top: 85.0 });
+        assert_eq!(serde_json::to_string(&bounds).unwrap(), "[-180.0,-85.0,180.0,85.0]");
+
+        assert!(serde_json::from_str::<Bounds>("[-180,-85,180]").is_err());
+        assert!(serde_json::from_str::<Bounds>("[-180,-85,180,85,0]").is_err());
+    }
+
+    #[test]
+    fn test_serde_lenient_coordinates() {
+        let expected = Bounds { left: -180.0, bottom: -85.05, right: 180.0, top: 85.05 };
+        for json in [
+            "[-180,-85.05,180,85.05]",
+            "[-180.0,-85.05,180.0,85.05]",
+            r#"["-180","-85.05","180"," 85.05 "]"#,
+            r#"[-180,"-85.05",180.0,"85.05"]"#,
+        ] {
+            assert_eq!(serde_json::from_str::<Bounds>(json).unwrap(), expected, "{}", json);
+        }
+
+        assert!(serde_json::from_str::<Bounds>(r#"["west",-85,180,85]"#).is_err());
+        assert!(serde_json::from_str::<Bounds>(r#"["NaN",-85,180,85]"#).is_err());
+        assert!(serde_json::from_str::<Bounds>("[null,-85,180,85]").is_err());
+    }
+}
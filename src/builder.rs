@@ -0,0 +1,167 @@
+use crate::bounds::Bounds;
+use crate::center::Center;
+use crate::tilejson::{Scheme, TileJson};
+
+/// Builds a `TileJson` field by field, starting from `TileJson::default()`.
+///
+/// ```
+/// use tilejson::TileJson;
+///
+/// let tilejson = TileJson::builder()
+///     .name("OpenStreetMap")
+///     .tile("https://a.tile.openstreetmap.org/{z}/{x}/{y}.png")
+///     .maxzoom(18)
+///     .build();
+/// assert_eq!(tilejson.tiles.len(), 1);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TileJsonBuilder {
+    tilejson: TileJson,
+}
+
+impl TileJsonBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn tilejson(mut self, tilejson: impl Into<String>) -> Self {
+        self.tilejson.tilejson = tilejson.into();
+        self
+    }
+
+    pub fn id(mut self, id: impl Into<String>) -> Self {
+        self.tilejson.id = Some(id.into());
+        self
+    }
+
+    pub fn name(mut self, name: impl Into<String>) -> Self {
+        self.tilejson.name = Some(name.into());
+        self
+    }
+
+    pub fn description(mut self, description: impl Into<String>) -> Self {
+        self.tilejson.description = Some(description.into());
+        self
+    }
+
+    pub fn version(mut self, version: impl Into<String>) -> Self {
+        self.tilejson.version = version.into();
+        self
+    }
+
+    pub fn attribution(mut self, attribution: impl Into<String>) -> Self {
+        self.tilejson.attribution = Some(attribution.into());
+        self
+    }
+
+    pub fn template(mut self, template: impl Into<String>) -> Self {
+        self.tilejson.template = Some(template.into());
+        self
+    }
+
+    pub fn legend(mut self, legend: impl Into<String>) -> Self {
+        self.tilejson.legend = Some(legend.into());
+        self
+    }
+
+    pub fn scheme(mut self, scheme: Scheme) -> Self {
+        self.tilejson.scheme = scheme;
+        self
+    }
+
+    /// Replaces all tile endpoints.
+    pub fn tiles(mut self, tiles: Vec<String>) -> Self {
+        self.tilejson.tiles = tiles;
+        self
+    }
+
+    /// Appends a single tile endpoint.
+    pub fn tile(mut self, tile: impl Into<String>) -> Self {
+        self.tilejson.tiles.push(tile.into());
+        self
+    }
+
+    pub fn grids(mut self, grids: Vec<String>) -> Self {
+        self.tilejson.grids = grids;
+        self
+    }
+
+    pub fn data(mut self, data: Vec<String>) -> Self {
+        self.tilejson.data = data;
+        self
+    }
+
+    pub fn minzoom(mut self, minzoom: u8) -> Self {
+        self.tilejson.minzoom = minzoom;
+        self
+    }
+
+    pub fn maxzoom(mut self, maxzoom: u8) -> Self {
+        self.tilejson.maxzoom = maxzoom;
+        self
+    }
+
+    pub fn fillzoom(mut self, fillzoom: u8) -> Self {
+        self.tilejson.fillzoom = Some(fillzoom);
+        self
+    }
+
+    pub fn bounds(mut self, bounds: Bounds) -> Self {
+        self.tilejson.bounds = Some(bounds);
+        self
+    }
+
+    pub fn center(mut self, center: Center) -> Self {
+        self.tilejson.center = Some(center);
+        self
+    }
+
+    pub fn tile_size(mut self, tile_size: u32) -> Self {
+        self.tilejson.tile_size = Some(tile_size);
+        self
+    }
+
+    pub fn format(mut self, format: impl Into<String>) -> Self {
+        self.tilejson.format = Some(format.into());
+        self
+    }
+
+    /// Returns the document. Nothing is checked here; see
+    /// `TileJson::validate`.
+    pub fn build(self) -> TileJson {
+        self.tilejson
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_builder() {
+        let tilejson = TileJsonBuilder::new()
+            .tilejson("3.0.0")
+            .name("Name")
+            .scheme(Scheme::TMS)
+            .tile("https://a.example.com/{z}/{x}/{y}.png")
+            .tile("https://b.example.com/{z}/{x}/{y}.png")
+            .minzoom(2)
+            .maxzoom(12)
+            .build();
+
+        let expected = TileJson {
+            tilejson: "3.0.0".to_owned(),
+            name: Some("Name".to_owned()),
+            scheme: Scheme::TMS,
+            tiles: vec![
+                "https://a.example.com/{z}/{x}/{y}.png".to_owned(),
+                "https://b.example.com/{z}/{x}/{y}.png".to_owned(),
+            ],
+            minzoom: 2,
+            maxzoom: 12,
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson, expected);
+        assert_eq!(TileJsonBuilder::new().build(), TileJson::default());
+    }
+}
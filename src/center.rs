@@ -0,0 +1,144 @@
+#[cfg(feature = "serde")]
+use crate::bounds::Coordinate;
+use crate::hash::hash_f64;
+#[cfg(feature = "serde")]
+use crate::mercator;
+
+#[cfg(feature = "serde")]
+use serde::de::{self, IgnoredAny, SeqAccess, Visitor};
+#[cfg(feature = "serde")]
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+#[cfg(feature = "serde")]
+use std::fmt;
+use std::hash::{Hash, Hasher};
+
+/// A `[longitude, latitude, zoom]` default location, in WGS:84 degrees and
+/// an integer zoom level. Older documents leave the zoom out, giving a
+/// `[longitude, latitude]` center with no `zoom`.
+///
+/// Centers are `Eq` and `Hash` as long as they hold no NaN: one that does
+/// isn't equal to itself.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Center {
+    pub longitude: f64,
+    pub latitude: f64,
+    pub zoom: Option<u8>,
+}
+
+impl Eq for Center {}
+
+impl Hash for Center {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        hash_f64(self.longitude, state);
+        hash_f64(self.latitude, state);
+        self.zoom.hash(state);
+    }
+}
+
+#[cfg(feature = "serde")]
+impl Serialize for Center {
+    /// Writes the `[longitude, latitude, zoom]` array form, leaving the zoom
+    /// out if there is none.
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self.zoom {
+            Some(zoom) => (self.longitude, self.latitude, zoom).serialize(serializer),
+            None => (self.longitude, self.latitude).serialize(serializer),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> Deserialize<'de> for Center {
+    /// Reads the `[longitude, latitude, zoom]` or `[longitude, latitude]`
+    /// array form, rejecting arrays that hold fewer than two or more than
+    /// three numbers. A float zoom such as `12.0` or `12.4` is rounded to the
+    /// nearest level, which must lie in `0..=30`. Each number may also be
+    /// given as a numeric string.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        deserializer.deserialize_seq(CenterVisitor)
+    }
+}
+
+#[cfg(feature = "serde")]
+struct CenterVisitor;
+
+#[cfg(feature = "serde")]
+impl<'de> Visitor<'de> for CenterVisitor {
+    type Value = Center;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a [longitude, latitude] or [longitude, latitude, zoom] array")
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Center, A::Error> {
+        let Coordinate(longitude) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(0, &self))?;
+        let Coordinate(latitude) = seq.next_element()?.ok_or_else(|| de::Error::invalid_length(1, &self))?;
+        let zoom = match seq.next_element()? {
+            Some(Coordinate(zoom)) => Some(zoom_level(zoom)?),
+            None => None,
+        };
+        if seq.next_element::<IgnoredAny>()?.is_some() {
+            return Err(de::Error::invalid_length(4, &self));
+        }
+        Ok(Center { longitude, latitude, zoom })
+    }
+}
+
+/// Rounds a center zoom to the nearest level, failing outside `0..=30`.
+#[cfg(feature = "serde")]
+fn zoom_level<E: de::Error>(zoom: f64) -> Result<u8, E> {
+    let level = zoom.round();
+    if !(0.0..=f64::from(mercator::MAX_ZOOM)).contains(&level) {
+        return Err(E::custom(format!("center zoom must lie in 0..=30, got {}", zoom)));
+    }
+    Ok(level as u8)
+}
+
+#[cfg(all(test, feature = "serde"))]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_serde() {
+        let center: Center = serde_json::from_str("[-122.4,37.8,12]").unwrap();
+        assert_eq!(center, Center { longitude: -122.4, latitude: 37.8, zoom: Some(12) });
+        assert_eq!(serde_json::to_string(&center).unwrap(), "[-122.4,37.8,12]");
+
+        assert!(serde_json::from_str::<Center>("[-122.4]").is_err());
+        assert!(serde_json::from_str::<Center>("[]").is_err());
+        assert!(serde_json::from_str::<Center>("[-122.4,37.8,12,0]").is_err());
+        assert!(serde_json::from_str::<Center>("[-122.4,37.8,-1]").is_err());
+    }
+
+    #[test]
+    fn test_serde_without_zoom() {
+        let center: Center = serde_json::from_str("[-122.4,37.8]").unwrap();
+        assert_eq!(center, Center { longitude: -122.4, latitude: 37.8, zoom: None });
+        assert_eq!(serde_json::to_string(&center).unwrap(), "[-122.4,37.8]");
+        assert_eq!(serde_json::from_str::<Center>(&serde_json::to_string(&center).unwrap()).unwrap(), center);
+
+        let center: Center = serde_json::from_str("[-122.4,37.8,12]").unwrap();
+        assert_eq!(serde_json::from_str::<Center>(&serde_json::to_string(&center).unwrap()).unwrap(), center);
+    }
+
+    #[test]
+    fn test_serde_lenient_coordinates() {
+        let expected = Center { longitude: -122.0, latitude: 37.8, zoom: Some(12) };
+        for json in ["[-122,37.8,12]", "[-122.0,37.8,12.0]", r#"["-122","37.8","12"]"#] {
+            assert_eq!(serde_json::from_str::<Center>(json).unwrap(), expected, "{}", json);
+        }
+        assert!(serde_json::from_str::<Center>(r#"["-122","north",12]"#).is_err());
+    }
+
+    #[test]
+    fn test_serde_float_zoom() {
+        for json in ["[-122.4,37.8,12]", "[-122.4,37.8,12.0]", "[-122.4,37.8,11.6]", "[-122.4,37.8,12.4]"] {
+            assert_eq!(serde_json::from_str::<Center>(json).unwrap().zoom, Some(12), "{}", json);
+        }
+        assert_eq!(serde_json::to_string(&serde_json::from_str::<Center>("[0,0,12.0]").unwrap()).unwrap(), "[0.0,0.0,12]");
+        assert_eq!(serde_json::from_str::<Center>("[0,0,30.0]").unwrap().zoom, Some(30));
+        assert!(serde_json::from_str::<Center>("[-122.4,37.8,40.0]").is_err());
+        assert!(serde_json::from_str::<Center>("[-122.4,37.8,31]").is_err());
+    }
+}
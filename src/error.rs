@@ -0,0 +1,269 @@
+use crate::bounds::Bounds;
+
+use std::error;
+use std::fmt;
+use std::io;
+
+/// An error raised while reading or decoding a `TileJson`.
+#[derive(Debug)]
+#[non_exhaustive]
+pub enum Error {
+    /// The underlying reader failed.
+    Io(io::Error),
+    /// The input isn't a valid TileJSON document.
+    #[cfg(feature = "serde")]
+    Json(serde_json::Error),
+    /// The document couldn't be encoded as CBOR.
+    #[cfg(feature = "cbor")]
+    CborEncode(ciborium::ser::Error<io::Error>),
+    /// The input isn't a valid CBOR-encoded TileJSON document.
+    #[cfg(feature = "cbor")]
+    CborDecode(ciborium::de::Error<io::Error>),
+    /// The document couldn't be encoded as MessagePack.
+    #[cfg(feature = "msgpack")]
+    MsgpackEncode(rmp_serde::encode::Error),
+    /// The input isn't a valid MessagePack-encoded TileJSON document.
+    #[cfg(feature = "msgpack")]
+    MsgpackDecode(rmp_serde::decode::Error),
+    /// The document was read but breaks a constraint of the spec.
+    Validation(ValidationError),
+}
+
+/// The former name of `Error`, kept so existing code keeps compiling.
+pub type TileJsonError = Error;
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Error::Io(err) => write!(f, "I/O error: {}", err),
+            #[cfg(feature = "serde")]
+            Error::Json(err) => write!(f, "JSON error: {}", err),
+            #[cfg(feature = "cbor")]
+            Error::CborEncode(err) => write!(f, "CBOR error: {}", err),
+            #[cfg(feature = "cbor")]
+            Error::CborDecode(err) => write!(f, "CBOR error: {}", err),
+            #[cfg(feature = "msgpack")]
+            Error::MsgpackEncode(err) => write!(f, "MessagePack error: {}", err),
+            #[cfg(feature = "msgpack")]
+            Error::MsgpackDecode(err) => write!(f, "MessagePack error: {}", err),
+            Error::Validation(err) => write!(f, "invalid TileJSON: {}", err),
+        }
+    }
+}
+
+impl error::Error for Error {
+    fn source(&self) -> Option<&(dyn error::Error + 'static)> {
+        match self {
+            Error::Io(err) => Some(err),
+            #[cfg(feature = "serde")]
+            Error::Json(err) => Some(err),
+            #[cfg(feature = "cbor")]
+            Error::CborEncode(err) => Some(err),
+            #[cfg(feature = "cbor")]
+            Error::CborDecode(err) => Some(err),
+            #[cfg(feature = "msgpack")]
+            Error::MsgpackEncode(err) => Some(err),
+            #[cfg(feature = "msgpack")]
+            Error::MsgpackDecode(err) => Some(err),
+            Error::Validation(err) => Some(err),
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(err: io::Error) -> Self {
+        Error::Io(err)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl From<serde_json::Error> for Error {
+    fn from(err: serde_json::Error) -> Self {
+        Error::Json(err)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl From<ciborium::ser::Error<io::Error>> for Error {
+    fn from(err: ciborium::ser::Error<io::Error>) -> Self {
+        Error::CborEncode(err)
+    }
+}
+
+#[cfg(feature = "cbor")]
+impl From<ciborium::de::Error<io::Error>> for Error {
+    fn from(err: ciborium::de::Error<io::Error>) -> Self {
+        Error::CborDecode(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::encode::Error> for Error {
+    fn from(err: rmp_serde::encode::Error) -> Self {
+        Error::MsgpackEncode(err)
+    }
+}
+
+#[cfg(feature = "msgpack")]
+impl From<rmp_serde::decode::Error> for Error {
+    fn from(err: rmp_serde::decode::Error) -> Self {
+        Error::MsgpackDecode(err)
+    }
+}
+
+impl From<ValidationError> for Error {
+    fn from(err: ValidationError) -> Self {
+        Error::Validation(err)
+    }
+}
+
+/// An error raised when parsing a string that doesn't name a `Scheme`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ParseSchemeError {
+    pub scheme: String,
+}
+
+impl fmt::Display for ParseSchemeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "unknown tile scheme {}, expected xyz or tms", self.scheme)
+    }
+}
+
+impl error::Error for ParseSchemeError {}
+
+/// A reason a `TileJson` doesn't meet the expectations placed on it.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum ValidationError {
+    /// An endpoint lacks the placeholders needed to address a tile.
+    MissingPlaceholder { url: String },
+
+    /// An endpoint uses a placeholder that can't be expressed in the format
+    /// it's being converted to, such as `{-y}` in a WMTS `ResourceURL`.
+    UnsupportedPlaceholder { url: String, placeholder: String },
+
+    /// An endpoint, with its placeholders filled in, isn't a valid URL.
+    #[cfg(feature = "url-validate")]
+    InvalidUrl { url: String, error: url::ParseError },
+
+    /// An endpoint references a host outside of the allowed set.
+    DisallowedHost { url: String, host: String },
+
+    /// The `tilejson` spec version isn't one of the supported ones.
+    UnsupportedVersion { version: String, supported: Vec<String> },
+
+    /// `field` holds a version that isn't a semver.org style version number.
+    InvalidVersion { field: &'static str, value: String },
+
+    /// `tiles` has no endpoint, while the spec requires at least one.
+    NoTiles,
+
+    /// `minzoom` is greater than `maxzoom`.
+    InvalidZoomRange { minzoom: u8, maxzoom: u8 },
+
+    /// `fillzoom` lies outside of `minzoom..=maxzoom`.
+    FillzoomOutOfRange { fillzoom: u8, minzoom: u8, maxzoom: u8 },
+
+    /// The zoom level held by `field` is above the highest one allowed.
+    ZoomOutOfRange { field: &'static str, zoom: u8, max_zoom: u8 },
+
+    /// `bounds` doesn't describe a non-empty `[left, bottom, right, top]`
+    /// area.
+    InvalidBounds { bounds: Bounds },
+
+    /// The zoom level of `center` lies outside of `minzoom..=maxzoom`.
+    CenterZoomOutOfRange { zoom: u8, minzoom: u8, maxzoom: u8 },
+
+    /// The longitude/latitude of `center` lies outside of `bounds`.
+    CenterOutOfBounds { longitude: f64, latitude: f64 },
+
+    /// Two documents being merged carry different values for the same extra
+    /// field.
+    ConflictingExtra { key: String },
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationError::MissingPlaceholder { url } => {
+                write!(f, "endpoint {} lacks a {{z}}, {{x}} or {{y}} placeholder", url)
+            }
+            ValidationError::UnsupportedPlaceholder { url, placeholder } => {
+                write!(f, "endpoint {} uses placeholder {{{}}}, which can't be converted", url, placeholder)
+            }
+            #[cfg(feature = "url-validate")]
+            ValidationError::InvalidUrl { url, error } => write!(f, "endpoint {} isn't a valid URL: {}", url, error),
+            ValidationError::DisallowedHost { url, host } => {
+                write!(f, "endpoint {} uses disallowed host {}", url, host)
+            }
+            ValidationError::UnsupportedVersion { version, supported } => {
+                write!(f, "unsupported TileJSON version {}, expected one of: {}", version, supported.join(", "))
+            }
+            ValidationError::InvalidVersion { field, value } => {
+                write!(f, "{} {} is not a semver version", field, value)
+            }
+            ValidationError::NoTiles => write!(f, "tiles has no endpoint"),
+            ValidationError::InvalidZoomRange { minzoom, maxzoom } => {
+                write!(f, "minzoom {} is greater than maxzoom {}", minzoom, maxzoom)
+            }
+            ValidationError::FillzoomOutOfRange { fillzoom, minzoom, maxzoom } => {
+                write!(f, "fillzoom {} lies outside of the zoom range {} to {}", fillzoom, minzoom, maxzoom)
+            }
+            ValidationError::ZoomOutOfRange { field, zoom, max_zoom } => {
+                write!(f, "{} {} is outside of the range 0 to {}", field, zoom, max_zoom)
+            }
+            ValidationError::InvalidBounds { bounds } => {
+                write!(f, "bounds {:?} don't describe an area", bounds)
+            }
+            ValidationError::CenterZoomOutOfRange { zoom, minzoom, maxzoom } => {
+                write!(f, "center zoom {} lies outside of the zoom range {} to {}", zoom, minzoom, maxzoom)
+            }
+            ValidationError::CenterOutOfBounds { longitude, latitude } => {
+                write!(f, "center [{}, {}] lies outside of bounds", longitude, latitude)
+            }
+            ValidationError::ConflictingExtra { key } => {
+                write!(f, "conflicting values for extra field {}", key)
+            }
+        }
+    }
+}
+
+impl error::Error for ValidationError {}
+
+/// A likely mistake in a `TileJson` that doesn't make it invalid.
+#[derive(Debug, PartialEq, Clone)]
+#[non_exhaustive]
+pub enum ValidationWarning {
+    /// An endpoint spells a placeholder in anything but lowercase, such as
+    /// `{Z}`, which strict clients won't substitute.
+    NonCanonicalPlaceholder { url: String },
+
+    /// `attribution` is longer than the given budget, in characters.
+    AttributionTooLong { len: usize, max_len: usize },
+
+    /// `attribution` contains control characters such as newlines.
+    AttributionControlCharacters,
+
+    /// `bounds` cover the whole world but `minzoom` is above 0, so the
+    /// basemap shows blank tiles when zoomed out.
+    NonZeroMinzoomGlobal { minzoom: u8 },
+}
+
+impl fmt::Display for ValidationWarning {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ValidationWarning::NonCanonicalPlaceholder { url } => {
+                write!(f, "endpoint {} has placeholders that aren't lowercase", url)
+            }
+            ValidationWarning::AttributionTooLong { len, max_len } => {
+                write!(f, "attribution is {} characters long, more than {}", len, max_len)
+            }
+            ValidationWarning::AttributionControlCharacters => {
+                write!(f, "attribution contains control characters")
+            }
+            ValidationWarning::NonZeroMinzoomGlobal { minzoom } => {
+                write!(f, "bounds are global but minzoom is {} rather than 0", minzoom)
+            }
+        }
+    }
+}
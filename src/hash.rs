@@ -0,0 +1,64 @@
+use std::hash::{Hash, Hasher};
+
+const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+/// A 64-bit FNV-1a hasher. Unlike `DefaultHasher` its output is fixed, so
+/// it's safe to persist or compare across builds.
+pub(crate) struct Fnv1a(u64);
+
+impl Default for Fnv1a {
+    fn default() -> Self {
+        Fnv1a(OFFSET_BASIS)
+    }
+}
+
+impl Hasher for Fnv1a {
+    fn finish(&self) -> u64 {
+        self.0
+    }
+
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 = (self.0 ^ u64::from(*byte)).wrapping_mul(PRIME);
+        }
+    }
+}
+
+/// Hashes a float by its bit pattern, with `-0.0` folded onto `0.0` so
+/// floats that compare equal hash alike. NaNs never compare equal, so how
+/// they hash doesn't matter.
+pub(crate) fn hash_f64<H: Hasher>(value: f64, state: &mut H) {
+    let value = if value == 0.0 { 0.0 } else { value };
+    value.to_bits().hash(state);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn fnv1a(bytes: &[u8]) -> u64 {
+        let mut hasher = Fnv1a::default();
+        hasher.write(bytes);
+        hasher.finish()
+    }
+
+    #[test]
+    fn test_fnv1a() {
+        assert_eq!(fnv1a(b""), 0xcbf2_9ce4_8422_2325);
+        assert_eq!(fnv1a(b"a"), 0xaf63_dc4c_8601_ec8c);
+        assert_eq!(fnv1a(b"foobar"), 0x8594_4171_f739_67e8);
+    }
+
+    #[test]
+    fn test_hash_f64() {
+        let hash = |value: f64| {
+            let mut hasher = Fnv1a::default();
+            hash_f64(value, &mut hasher);
+            hasher.finish()
+        };
+        assert_eq!(hash(0.0), hash(-0.0));
+        assert_eq!(hash(85.0511), hash(85.0511));
+        assert_ne!(hash(1.0), hash(-1.0));
+    }
+}
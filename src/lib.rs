@@ -1,6 +1,62 @@
+#[cfg(any(feature = "arbitrary", feature = "proptest"))]
+mod arbitrary;
+mod bounds;
+mod builder;
+mod center;
+mod error;
+mod hash;
+mod mercator;
+mod mustache;
+#[cfg(feature = "jsonschema")]
+mod schema;
 mod tilejson;
+mod url_template;
 
+pub use crate::bounds::Bounds as Bounds;
+pub use crate::builder::TileJsonBuilder as TileJsonBuilder;
+pub use crate::center::Center as Center;
+pub use crate::error::Error as Error;
+pub use crate::error::TileJsonError as TileJsonError;
+pub use crate::error::ParseSchemeError as ParseSchemeError;
+pub use crate::error::ValidationError as ValidationError;
+pub use crate::error::ValidationWarning as ValidationWarning;
+pub use crate::mercator::lnglat_to_tile as lnglat_to_tile;
+pub use crate::mercator::scale_denominator as scale_denominator;
+pub use crate::mercator::tile_bounds as tile_bounds;
+#[cfg(feature = "jsonschema")]
+pub use crate::schema::validate_schema as validate_schema;
+#[cfg(feature = "jsonschema")]
+pub use crate::schema::TILEJSON_SCHEMA as TILEJSON_SCHEMA;
 pub use crate::tilejson::TileJson as TileJson;
 pub use crate::tilejson::Scheme as Scheme;
-pub use crate::tilejson::decode as decode;
-pub use crate::tilejson::encode as encode;
\ No newline at end of file
+pub use crate::tilejson::FieldChange as FieldChange;
+pub use crate::tilejson::Orientation as Orientation;
+pub use crate::tilejson::SpecVersion as SpecVersion;
+pub use crate::tilejson::MergePolicy as MergePolicy;
+pub use crate::tilejson::ExtrasPolicy as ExtrasPolicy;
+pub use crate::tilejson::VectorLayer as VectorLayer;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode as decode;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_lenient as decode_lenient;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_with as decode_with;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::DecodeOptions as DecodeOptions;
+pub use crate::tilejson::from_xyz_url as from_xyz_url;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::from_mbtiles_metadata as from_mbtiles_metadata;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_bytes as decode_bytes;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_reader as decode_reader;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_many as decode_many;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::decode_stream as decode_stream;
+#[cfg(feature = "tokio")]
+pub use crate::tilejson::json::decode_async_reader as decode_async_reader;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::encode as encode;
+#[cfg(feature = "serde")]
+pub use crate::tilejson::json::encode_pretty as encode_pretty;
\ No newline at end of file
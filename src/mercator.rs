@@ -0,0 +1,344 @@
+use crate::bounds::Bounds;
+
+use std::f64::consts::PI;
+
+/// The highest zoom level allowed by the TileJSON spec.
+pub(crate) const MAX_ZOOM: u32 = 30;
+
+/// The latitude limit of the Spherical Mercator projection, in degrees.
+pub(crate) const MAX_LATITUDE: f64 = 85.051_128_779_806_59;
+
+/// The equatorial radius of the WGS:84 ellipsoid, in meters.
+const EARTH_RADIUS: f64 = 6_378_137.0;
+
+/// The mean radius of the Earth, in kilometers.
+const MEAN_EARTH_RADIUS_KM: f64 = 6371.0088;
+
+/// The OGC standardized rendering pixel size, in meters.
+const STANDARD_PIXEL_SIZE: f64 = 0.00028;
+
+/// The tile size, in pixels, of the OGC WebMercatorQuad tile matrix set.
+const WEB_MERCATOR_QUAD_TILE_SIZE: f64 = 256.0;
+
+/// Returns the scale denominator of zoom level `z` in the OGC
+/// WebMercatorQuad tile matrix set, as advertised by WMTS `ScaleDenominator`.
+pub fn scale_denominator(z: u32) -> f64 {
+    let resolution = 2.0 * PI * EARTH_RADIUS / (WEB_MERCATOR_QUAD_TILE_SIZE * tiles_per_side(z));
+    resolution / STANDARD_PIXEL_SIZE
+}
+
+/// Returns the XYZ tile `(x, y)` containing the WGS:84 longitude/latitude
+/// `lng`/`lat` at zoom `z` in the Spherical Mercator tiling the spec assumes.
+/// Latitudes beyond the mercator limit of about ±85.0511 are clamped onto the
+/// edge rows, longitudes outside [-180, 180] onto the edge columns, and zooms
+/// above 30 down to 30.
+pub fn lnglat_to_tile(lng: f64, lat: f64, z: u32) -> (u32, u32) {
+    lonlat_to_tile(lng, lat, z.min(MAX_ZOOM))
+}
+
+/// Returns the WGS:84 extent of the XYZ tile `z`/`x`/`y` in the Spherical
+/// Mercator tiling the spec assumes. Zooms above 30 are clamped down to 30,
+/// and `x` and `y` onto the last column and row of the zoom level.
+pub fn tile_bounds(z: u32, x: u32, y: u32) -> Bounds {
+    let z = z.min(MAX_ZOOM);
+    let last = (1 << z) - 1;
+    Bounds::from(tile_bbox(z, x.min(last), y.min(last)))
+}
+
+/// Converts a WGS:84 longitude/latitude into the XYZ tile containing it at
+/// zoom `z`. Latitudes beyond the mercator limit are clamped onto the edge
+/// rows, longitudes outside [-180, 180] onto the edge columns.
+pub(crate) fn lonlat_to_tile(lon: f64, lat: f64, z: u32) -> (u32, u32) {
+    let n = tiles_per_side(z);
+    let (x, y) = lonlat_to_tile_fraction(lon, lat, z);
+    (clamp_index(x.floor(), n), clamp_index(y.floor(), n))
+}
+
+/// Like `lonlat_to_tile`, but keeps the position within the tile as the
+/// fractional part.
+pub(crate) fn lonlat_to_tile_fraction(lon: f64, lat: f64, z: u32) -> (f64, f64) {
+    let n = tiles_per_side(z);
+    let lat = lat.clamp(-MAX_LATITUDE, MAX_LATITUDE).to_radians();
+    let x = (lon.clamp(-180.0, 180.0) + 180.0) / 360.0 * n;
+    let y = (1.0 - (lat.tan() + 1.0 / lat.cos()).ln() / PI) / 2.0 * n;
+    (x, y)
+}
+
+/// The XYZ tiles covering a bounding box at a single zoom level.
+#[derive(Debug, PartialEq, Clone)]
+pub(crate) struct TileExtent {
+    pub z: u32,
+    /// Inclusive column ranges. There are two of them when the box crosses
+    /// the antimeridian.
+    pub columns: Vec<(u32, u32)>,
+    /// Inclusive row range.
+    pub rows: (u32, u32),
+}
+
+impl TileExtent {
+    /// Computes the extent of `[left, bottom, right, top]` at zoom `z`. A
+    /// `left` greater than `right` means the box crosses the antimeridian.
+    /// Tiles only touching the right or bottom edge are not included.
+    pub fn new(bbox: [f64; 4], z: u32) -> Self {
+        let [left, bottom, right, top] = bbox;
+        let n = tiles_per_side(z);
+        let (min_x, min_y) = lonlat_to_tile(left, top, z);
+        let (right_x, bottom_y) = lonlat_to_tile_fraction(right, bottom, z);
+        let max_x = clamp_index(right_x.ceil() - 1.0, n);
+        let max_y = clamp_index(bottom_y.ceil() - 1.0, n).max(min_y);
+
+        let columns = if left > right {
+            vec![(min_x, clamp_index(n - 1.0, n)), (0, max_x)]
+        } else {
+            vec![(min_x, max_x.max(min_x))]
+        };
+        TileExtent { z, columns, rows: (min_y, max_y) }
+    }
+
+    /// Grows the extent by `margin` tiles on every side, without going past
+    /// the edges of the world.
+    pub fn buffered(self, margin: u32) -> Self {
+        let last = (1u32 << self.z) - 1;
+        let grow = |(min, max): (u32, u32)| (min.saturating_sub(margin), max.saturating_add(margin).min(last));
+        let columns = match *self.columns.as_slice() {
+            [(east_min, _), (_, west_max)] => vec![grow((east_min, last)), grow((0, west_max))],
+            _ => self.columns.into_iter().map(grow).collect(),
+        };
+        TileExtent { z: self.z, columns, rows: grow(self.rows) }
+    }
+
+    /// The `(x, y)` of every tile in the extent, column by column.
+    pub fn tiles(self) -> impl Iterator<Item = (u32, u32)> {
+        let (min_y, max_y) = self.rows;
+        self.columns
+            .into_iter()
+            .flat_map(|(min_x, max_x)| min_x..=max_x)
+            .flat_map(move |x| (min_y..=max_y).map(move |y| (x, y)))
+    }
+
+    /// The number of tiles in the extent.
+    pub fn count(&self) -> u64 {
+        let rows = u64::from(self.rows.1 - self.rows.0) + 1;
+        let columns: u64 = self.columns.iter().map(|(min, max)| u64::from(max - min) + 1).sum();
+        rows * columns
+    }
+}
+
+/// Returns the `[left, bottom, right, top]` WGS:84 extent of an XYZ tile.
+pub(crate) fn tile_bbox(z: u32, x: u32, y: u32) -> [f64; 4] {
+    let n = tiles_per_side(z);
+    let lon = |x: f64| x / n * 360.0 - 180.0;
+    let lat = |y: f64| (PI * (1.0 - 2.0 * y / n)).sinh().atan().to_degrees();
+    let (x, y) = (f64::from(x), f64::from(y));
+    [lon(x), lat(y + 1.0), lon(x + 1.0), lat(y)]
+}
+
+/// Returns the `[left, bottom, right, top]` box enclosing the circle of
+/// great-circle radius `radius_km` around a point. The box crosses the
+/// antimeridian (`left > right`) when the circle does, and spans all
+/// longitudes when the circle contains a pole.
+pub(crate) fn circle_bbox(lon: f64, lat: f64, radius_km: f64) -> [f64; 4] {
+    let distance = radius_km / MEAN_EARTH_RADIUS_KM;
+    let bottom = lat - distance.to_degrees();
+    let top = lat + distance.to_degrees();
+    if bottom <= -90.0 || top >= 90.0 {
+        return [-180.0, bottom.max(-90.0), 180.0, top.min(90.0)];
+    }
+
+    let delta = (distance.sin() / lat.to_radians().cos()).asin().to_degrees();
+    if delta.is_nan() || delta >= 180.0 {
+        return [-180.0, bottom, 180.0, top];
+    }
+    let wrap = |lon: f64| if lon < -180.0 { lon + 360.0 } else if lon > 180.0 { lon - 360.0 } else { lon };
+    [wrap(lon - delta), bottom, wrap(lon + delta), top]
+}
+
+/// Returns the great-circle distance in kilometers from a point to the
+/// nearest point of a `[left, bottom, right, top]` box not crossing the
+/// antimeridian, approximating the nearest point by clamping.
+pub(crate) fn distance_to_bbox_km(lon: f64, lat: f64, [left, bottom, right, top]: [f64; 4]) -> f64 {
+    let nearest_lon = if left <= lon && lon <= right {
+        lon
+    } else if (left - lon).rem_euclid(360.0) < (lon - right).rem_euclid(360.0) {
+        left
+    } else {
+        right
+    };
+    haversine_km(lon, lat, nearest_lon, lat.clamp(bottom, top))
+}
+
+fn haversine_km(lon1: f64, lat1: f64, lon2: f64, lat2: f64) -> f64 {
+    let (lat1, lat2) = (lat1.to_radians(), lat2.to_radians());
+    let dlat = lat2 - lat1;
+    let dlon = (lon2 - lon1).to_radians();
+    let a = (dlat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (dlon / 2.0).sin().powi(2);
+    2.0 * MEAN_EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+}
+
+/// Whether `x`/`y` address one of the tiles at zoom `z`, for zooms up to
+/// `MAX_ZOOM`.
+pub(crate) fn is_tile(z: u32, x: u32, y: u32) -> bool {
+    z <= MAX_ZOOM && x < 1 << z && y < 1 << z
+}
+
+/// Flips a tile row between the XYZ and TMS numbering at zoom `z`, or
+/// `None` if `y` isn't a row at that zoom.
+pub(crate) fn flip_y(z: u32, y: u32) -> Option<u32> {
+    1u32.checked_shl(z)?.checked_sub(y)?.checked_sub(1)
+}
+
+/// The Bing Maps quadkey of the XYZ tile `z`/`x`/`y`: one base-4 digit per
+/// zoom level, most significant first, with the `x` bit counting 1 and the
+/// `y` bit 2. Zoom 0 has the empty quadkey.
+pub(crate) fn quadkey(z: u32, x: u32, y: u32) -> String {
+    (1..=z)
+        .rev()
+        .map(|level| {
+            let mask = 1 << (level - 1);
+            let digit = u8::from(x & mask != 0) + 2 * u8::from(y & mask != 0);
+            char::from(b'0' + digit)
+        })
+        .collect()
+}
+
+fn tiles_per_side(z: u32) -> f64 {
+    2f64.powi(z as i32)
+}
+
+fn clamp_index(value: f64, n: f64) -> u32 {
+    value.clamp(0.0, n - 1.0) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_lonlat_to_tile() {
+        assert_eq!(lonlat_to_tile(0.0, 0.0, 0), (0, 0));
+        assert_eq!(lonlat_to_tile(0.0, 0.0, 1), (1, 1));
+        assert_eq!(lonlat_to_tile(-180.0, 90.0, 2), (0, 0));
+        assert_eq!(lonlat_to_tile(180.0, -90.0, 2), (3, 3));
+        assert_eq!(lonlat_to_tile(-122.4194, 37.7749, 10), (163, 395));
+    }
+
+    #[test]
+    fn test_lnglat_to_tile() {
+        assert_eq!(lnglat_to_tile(0.0, 0.0, 0), (0, 0));
+        assert_eq!(lnglat_to_tile(0.0, 0.0, 1), (1, 1));
+        assert_eq!(lnglat_to_tile(-0.1, 0.1, 1), (0, 0));
+        assert_eq!(lnglat_to_tile(10.0, 89.9, 3), lnglat_to_tile(10.0, MAX_LATITUDE, 3));
+        assert_eq!(lnglat_to_tile(179.9, 0.0, 40), lnglat_to_tile(179.9, 0.0, MAX_ZOOM));
+        assert_eq!(lnglat_to_tile(180.0, 0.0, 40), ((1 << 30) - 1, 1 << 29));
+    }
+
+    #[test]
+    fn test_tile_bounds() {
+        let bounds = tile_bounds(0, 0, 0);
+        assert_eq!([bounds.left, bounds.right], [-180.0, 180.0]);
+        assert!((bounds.bottom + MAX_LATITUDE).abs() < 1e-9 && (bounds.top - MAX_LATITUDE).abs() < 1e-9);
+
+        let bounds = tile_bounds(1, 1, 1);
+        assert_eq!([bounds.left, bounds.top, bounds.right], [0.0, 0.0, 180.0]);
+        assert!((bounds.bottom + MAX_LATITUDE).abs() < 1e-9);
+
+        assert_eq!(tile_bounds(1, 5, 7), tile_bounds(1, 1, 1));
+        assert_eq!(tile_bounds(40, 0, 0), tile_bounds(MAX_ZOOM, 0, 0));
+    }
+
+    #[test]
+    fn test_tile_extent() {
+        let extent = TileExtent::new([0.0, 0.0, 90.0, 45.0], 3);
+        assert_eq!(extent.columns, vec![(4, 5)]);
+        assert_eq!(extent.rows, (2, 3));
+        assert_eq!(extent.count(), 4);
+        assert_eq!(extent.tiles().collect::<Vec<_>>(), vec![(4, 2), (4, 3), (5, 2), (5, 3)]);
+
+        let extent = TileExtent::new([170.0, -10.0, -170.0, 10.0], 2);
+        assert_eq!(extent.columns, vec![(3, 3), (0, 0)]);
+        assert_eq!(extent.rows, (1, 2));
+        assert_eq!(extent.count(), 4);
+        assert_eq!(extent.tiles().collect::<Vec<_>>(), vec![(3, 1), (3, 2), (0, 1), (0, 2)]);
+
+        assert_eq!(TileExtent::new([-180.0, -90.0, 180.0, 90.0], 0).count(), 1);
+    }
+
+    #[test]
+    fn test_tile_extent_buffered() {
+        let extent = TileExtent::new([0.0, 0.0, 90.0, 45.0], 3).buffered(1);
+        assert_eq!(extent.columns, vec![(3, 6)]);
+        assert_eq!(extent.rows, (1, 4));
+
+        let extent = TileExtent::new([-180.0, 0.0, -135.0, 45.0], 3).buffered(2);
+        assert_eq!(extent.columns, vec![(0, 2)]);
+        assert_eq!(extent.rows, (0, 5));
+
+        let extent = TileExtent::new([170.0, -10.0, -170.0, 10.0], 2).buffered(1);
+        assert_eq!(extent.columns, vec![(2, 3), (0, 1)]);
+        assert_eq!(extent.rows, (0, 3));
+    }
+
+    #[test]
+    fn test_scale_denominator() {
+        assert!((scale_denominator(0) - 559_082_264.028_717_8).abs() < 1e-6);
+        assert!((scale_denominator(1) - 279_541_132.014_358_9).abs() < 1e-6);
+    }
+
+    #[test]
+    fn test_tile_bbox() {
+        let [left, bottom, right, top] = tile_bbox(0, 0, 0);
+        assert_eq!([left, right], [-180.0, 180.0]);
+        assert!((bottom + MAX_LATITUDE).abs() < 1e-9 && (top - MAX_LATITUDE).abs() < 1e-9);
+
+        let [left, bottom, right, top] = tile_bbox(1, 1, 0);
+        assert_eq!([left, bottom, right], [0.0, 0.0, 180.0]);
+        assert!((top - MAX_LATITUDE).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_circle_bbox() {
+        let [left, bottom, right, top] = circle_bbox(0.0, 0.0, 111.195);
+        assert!((left + 1.0).abs() < 1e-3 && (right - 1.0).abs() < 1e-3);
+        assert!((bottom + 1.0).abs() < 1e-3 && (top - 1.0).abs() < 1e-3);
+
+        let [left, _, right, _] = circle_bbox(179.5, 0.0, 111.195);
+        assert!((left - 178.5).abs() < 1e-3 && (right + 179.5).abs() < 1e-3);
+
+        assert_eq!(circle_bbox(0.0, 89.5, 200.0)[0], -180.0);
+        assert_eq!(circle_bbox(0.0, 89.5, 200.0)[3], 90.0);
+    }
+
+    #[test]
+    fn test_distance_to_bbox_km() {
+        assert_eq!(distance_to_bbox_km(0.5, 0.5, [0.0, 0.0, 1.0, 1.0]), 0.0);
+        assert!((distance_to_bbox_km(0.0, 0.0, [1.0, -1.0, 2.0, 1.0]) - 111.195).abs() < 1e-2);
+        assert!((distance_to_bbox_km(179.5, 0.0, [-180.0, -1.0, -179.0, 1.0]) - 55.597).abs() < 1e-2);
+    }
+
+    #[test]
+    fn test_is_tile() {
+        assert!(is_tile(0, 0, 0));
+        assert!(is_tile(2, 3, 3));
+        assert!(!is_tile(2, 4, 0));
+        assert!(!is_tile(2, 0, 5));
+        assert!(is_tile(30, (1 << 30) - 1, 0));
+        assert!(!is_tile(31, 0, 0));
+    }
+
+    #[test]
+    fn test_flip_y() {
+        assert_eq!(flip_y(0, 0), Some(0));
+        assert_eq!(flip_y(2, 1), Some(2));
+        assert_eq!(flip_y(2, 3).and_then(|y| flip_y(2, y)), Some(3));
+        assert_eq!(flip_y(2, 4), None);
+        assert_eq!(flip_y(32, 0), None);
+    }
+
+    #[test]
+    fn test_quadkey() {
+        assert_eq!(quadkey(3, 3, 5), "213");
+        assert_eq!(quadkey(0, 0, 0), "");
+        assert_eq!(quadkey(1, 1, 0), "1");
+        assert_eq!(quadkey(2, 3, 3), "33");
+    }
+}
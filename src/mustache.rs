@@ -0,0 +1,56 @@
+/// The UTFGrid formatter sections, which select how to display an
+/// interaction rather than reference grid data.
+const UTFGRID_SECTIONS: [&str; 3] = ["__location__", "__teaser__", "__full__"];
+
+/// Returns the names referenced by the tags of a mustache template, in order
+/// of first appearance and without duplicates.
+///
+/// Variables (`{{name}}`, `{{{name}}}`, `{{&name}}`) and sections
+/// (`{{#name}}`, `{{^name}}`) count; closing tags, comments, partials, the
+/// implicit iterator `{{.}}` and the UTFGrid formatter sections don't.
+/// Custom delimiters aren't supported.
+pub(crate) fn variables(template: &str) -> Vec<String> {
+    let mut names: Vec<String> = Vec::new();
+    let mut rest = template;
+    while let Some(start) = rest.find("{{") {
+        let after = &rest[start + 2..];
+        let (tag, remainder) = match after.strip_prefix('{') {
+            Some(triple) => match triple.find("}}}") {
+                Some(end) => (&triple[..end], &triple[end + 3..]),
+                None => break,
+            },
+            None => match after.find("}}") {
+                Some(end) => (&after[..end], &after[end + 2..]),
+                None => break,
+            },
+        };
+        rest = remainder;
+
+        let tag = tag.trim();
+        let name = match tag.chars().next() {
+            Some('#') | Some('^') | Some('&') => tag[1..].trim(),
+            Some('/') | Some('!') | Some('>') | Some('=') | None => continue,
+            Some(_) => tag,
+        };
+        if name.is_empty() || name == "." || UTFGRID_SECTIONS.contains(&name) {
+            continue;
+        }
+        if !names.iter().any(|known| known == name) {
+            names.push(name.to_owned());
+        }
+    }
+    names
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_variables() {
+        assert_eq!(variables("no tags"), Vec::<String>::new());
+        assert_eq!(variables("{{ NAME }} ({{{POP}}}) {{&NAME}}"), vec!["NAME", "POP"]);
+        assert_eq!(variables("{{! comment }}{{> partial}}{{#list}}{{.}}{{/list}}"), vec!["list"]);
+        assert_eq!(variables("{{unterminated"), Vec::<String>::new());
+    }
+}
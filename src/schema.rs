@@ -0,0 +1,90 @@
+//! Validation of raw documents against the TileJSON JSON Schema, behind the
+//! `jsonschema` feature.
+
+use serde_json::Value;
+
+use std::sync::OnceLock;
+
+/// The TileJSON JSON Schema, after the one published with the 3.0.0 spec
+/// but with `vector_layers` optional, as it is in 2.x documents.
+pub const TILEJSON_SCHEMA: &str = r#"{
+  "$schema": "http://json-schema.org/draft-07/schema#",
+  "title": "TileJSON",
+  "type": "object",
+  "properties": {
+    "tilejson": { "type": "string", "pattern": "^\\d+\\.\\d+\\.\\d+\\w?[\\w\\d]*$" },
+    "id": { "type": "string" },
+    "name": { "type": "string" },
+    "description": { "type": "string" },
+    "version": { "type": "string", "pattern": "^\\d+\\.\\d+\\.\\d+\\w?[\\w\\d]*$" },
+    "attribution": { "type": "string" },
+    "template": { "type": "string" },
+    "legend": { "type": "string" },
+    "scheme": { "type": "string", "enum": ["xyz", "tms"] },
+    "tiles": { "type": "array", "items": { "type": "string" } },
+    "grids": { "type": "array", "items": { "type": "string" } },
+    "data": { "type": "array", "items": { "type": "string" } },
+    "minzoom": { "type": "integer", "minimum": 0, "maximum": 30 },
+    "maxzoom": { "type": "integer", "minimum": 0, "maximum": 30 },
+    "fillzoom": { "type": "integer", "minimum": 0, "maximum": 30 },
+    "bounds": { "type": "array", "items": { "type": "number" }, "minItems": 4, "maxItems": 4 },
+    "center": { "type": "array", "items": { "type": "number" }, "minItems": 2, "maxItems": 3 },
+    "vector_layers": {
+      "type": "array",
+      "items": {
+        "type": "object",
+        "properties": {
+          "id": { "type": "string" },
+          "fields": { "type": "object", "additionalProperties": { "type": "string" } },
+          "description": { "type": "string" },
+          "minzoom": { "type": "integer", "minimum": 0, "maximum": 30 },
+          "maxzoom": { "type": "integer", "minimum": 0, "maximum": 30 }
+        },
+        "required": ["id", "fields"]
+      }
+    }
+  },
+  "required": ["tilejson", "tiles"]
+}"#;
+
+/// Validates a raw JSON document against `TILEJSON_SCHEMA`, catching
+/// structural problems before `decode`.
+///
+/// Returns every schema violation found, or a single message if `json`
+/// isn't valid JSON at all.
+pub fn validate_schema(json: &str) -> Result<(), Vec<String>> {
+    static VALIDATOR: OnceLock<jsonschema::Validator> = OnceLock::new();
+
+    let instance: Value = serde_json::from_str(json).map_err(|err| vec![err.to_string()])?;
+    let validator = VALIDATOR.get_or_init(|| {
+        let schema = serde_json::from_str(TILEJSON_SCHEMA).expect("the TileJSON schema is valid JSON");
+        jsonschema::validator_for(&schema).expect("the TileJSON schema is a valid schema")
+    });
+    let errors: Vec<String> = validator.iter_errors(&instance).map(|err| err.to_string()).collect();
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_schema() {
+        let valid = r#"{"tilejson":"2.2.0","tiles":["https://example.com/{z}/{x}/{y}.png"],"minzoom":0,"maxzoom":14}"#;
+        assert_eq!(validate_schema(valid), Ok(()));
+        assert_eq!(validate_schema(include_str!("../examples/example2.2.0.json")), Ok(()));
+
+        let errors = validate_schema(r#"{"tiles":["https://example.com/{z}/{x}/{y}.png"]}"#).unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(errors[0].contains("tilejson"), "{}", errors[0]);
+
+        let errors = validate_schema(r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":31,"scheme":"wmts"}"#).unwrap_err();
+        assert_eq!(errors.len(), 2);
+
+        assert_eq!(validate_schema("{").unwrap_err().len(), 1);
+    }
+}
@@ -1,23 +1,67 @@
+#[cfg(feature = "serde")]
 use serde::{Serialize, Deserialize};
+#[cfg(feature = "serde")]
+use serde_json::{Map, Value};
+
+use crate::bounds::{self, Bounds};
+use crate::builder::TileJsonBuilder;
+use crate::center::Center;
+use crate::error::{Error, ParseSchemeError, ValidationError, ValidationWarning};
+use crate::hash::Fnv1a;
+use crate::mercator::{self, TileExtent};
+use crate::mustache;
+use crate::url_template::{Segment, UrlTemplate};
 
 use std::collections::HashMap;
+use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::str::FromStr;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+#[cfg(any(feature = "cbor", feature = "msgpack"))]
+mod binary;
+#[cfg(feature = "serde")]
+pub(crate) mod json;
+#[cfg(feature = "html-sanitize")]
+mod sanitize;
+#[cfg(feature = "url-validate")]
+mod urls;
+#[cfg(feature = "wmts")]
+mod wmts;
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
+/// A TileJSON document.
+///
+/// The struct is `#[non_exhaustive]`, since new versions of the spec and
+/// its extensions keep adding fields: outside of this crate, build documents
+/// with `TileJson::new`, `TileJson::builder` or `TileJson::default` and
+/// then set fields, rather than with struct literals.
+///
+/// Documents are `Eq` and `Hash`, so they can be deduplicated in sets. A
+/// document holding a NaN coordinate in `bounds` or `center` isn't equal to
+/// itself, so such documents are never deduplicated.
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(PartialEq, Eq, Hash, Clone)]
+#[non_exhaustive]
 pub struct TileJson {
     /// REQUIRED. A semver.org style version number. Describes the version of
     /// the TileJSON spec that is implemented by this JSON object.
     pub tilejson: String,
 
+    /// OPTIONAL. Default: null. An identifier of the tileset used by tile
+    /// registries such as Mapbox. This is an extension to the spec.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub id: Option<String>,
+
     /// OPTIONAL. Default: null. A name describing the tileset. The name can
     /// contain any legal character. Implementations SHOULD NOT interpret the
     /// name as HTML.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub name: Option<String>,
 
     /// OPTIONAL. Default: null. A text description of the tileset. The
     /// description can contain any legal character. Implementations SHOULD NOT
     /// interpret the description as HTML.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub description: Option<String>,
 
     /// OPTIONAL. Default: "1.0.0". A semver.org style version number. When
@@ -27,34 +71,34 @@ pub struct TileJson {
     /// level MUST only have changes to tiles that are contained within one tile.
     /// When tiles change significantly, the major version MUST be increased.
     /// Implementations MUST NOT use tiles with different major versions.
-    #[serde(default = "default_version")]
+    #[cfg_attr(feature = "serde", serde(default = "default_version"))]
     pub version: String,
 
     /// OPTIONAL. Default: null. Contains an attribution to be displayed
     /// when the map is shown to a user. Implementations MAY decide to treat this
     /// as HTML or literal text. For security reasons, make absolutely sure that
     /// this field can't be abused as a vector for XSS or beacon tracking.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub attribution: Option<String>,
 
     /// OPTIONAL. Default: null. Contains a mustache template to be used to
     /// format data from grids for interaction.
     /// See https:///github.com/mapbox/utfgrid-spec/tree/master/1.2
     /// for the interactivity specification.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub template: Option<String>,
 
     /// OPTIONAL. Default: null. Contains a legend to be displayed with the map.
     /// Implementations MAY decide to treat this as HTML or literal text.
     /// For security reasons, make absolutely sure that this field can't be
     /// abused as a vector for XSS or beacon tracking.
-    #[serde(skip_serializing_if = "Option::is_none")]
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
     pub legend: Option<String>,
 
     /// OPTIONAL. Default: "xyz". Either "xyz" or "tms". Influences the y
     /// direction of the tile coordinates.
     /// The global-mercator (aka Spherical Mercator) profile is assumed.
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default))]
     pub scheme: Scheme,
 
     /// REQUIRED. An array of tile endpoints. {z}, {x} and {y}, if present,
@@ -71,7 +115,10 @@ pub struct TileJson {
     /// for this tileset.
     /// See https:///github.com/mapbox/utfgrid-spec/tree/master/1.2
     /// for the interactivity specification.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "json::string_or_vec")
+    )]
     pub grids: Vec<String>,
 
     /// OPTIONAL. Default: []. An array of data files in GeoJSON format.
@@ -81,26 +128,50 @@ pub struct TileJson {
     /// All endpoints MUST return the same content for the same URL.
     /// If the array doesn't contain any entries, then no data is present in
     /// the map.
-    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default, skip_serializing_if = "Vec::is_empty", deserialize_with = "json::string_or_vec")
+    )]
     pub data: Vec<String>,
 
     /// OPTIONAL. Default: 0. >= 0, <= 30.
     /// An integer specifying the minimum zoom level.
-    #[serde(default = "default_minzoom")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_minzoom", deserialize_with = "json::string_or_number")
+    )]
     pub minzoom: u8,
 
     /// OPTIONAL. Default: 30. >= 0, <= 30.
     /// An integer specifying the maximum zoom level. MUST be >= minzoom.
-    #[serde(default = "default_maxzoom")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_maxzoom", deserialize_with = "json::string_or_number")
+    )]
     pub maxzoom: u8,
 
+    /// OPTIONAL. Default: null. >= minzoom, <= maxzoom.
+    /// An integer specifying the zoom level from which to generate overzoomed
+    /// tiles, rather than requesting new ones from the server.
+    #[cfg_attr(
+        feature = "serde",
+        serde(
+            default,
+            skip_serializing_if = "Option::is_none",
+            deserialize_with = "json::option_string_or_number"
+        )
+    )]
+    pub fillzoom: Option<u8>,
+
     /// OPTIONAL. Default: [-180, -90, 180, 90].
     /// The maximum extent of available map tiles. Bounds MUST define an area
     /// covered by all zoom levels. The bounds are represented in WGS:84
     /// latitude and longitude values, in the order left, bottom, right, top.
     /// Values may be integers or floating point numbers.
-    #[serde(default = "default_bounds")]
-    pub bounds: Vec<f64>,
+    /// `None` when the document doesn't declare any, see
+    /// `TileJson::bounds_or_default`.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub bounds: Option<Bounds>,
 
     /// OPTIONAL. Default: null.
     /// The first value is the longitude, the second is latitude (both in
@@ -110,29 +181,57 @@ pub struct TileJson {
     /// Implementations can use this value to set the default location. If the
     /// value is null, implementations may use their own algorithm for
     /// determining a default location.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub center: Option<Vec<f64>>,
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub center: Option<Center>,
+
+    /// OPTIONAL. Default: 256. The width and height of the tiles in pixels,
+    /// e.g. 512 for retina tilesets. This is an extension to the spec.
+    #[cfg_attr(
+        feature = "serde",
+        serde(rename = "tileSize", skip_serializing_if = "Option::is_none")
+    )]
+    pub tile_size: Option<u32>,
 
     /// Optional. This seems to only Mapbox specific field to show if Mapbox watermark should be
     /// displayed or not.
-    #[serde(default = "default_mapbox_logo")]
+    #[cfg_attr(
+        feature = "serde",
+        serde(default = "default_mapbox_logo", skip_serializing_if = "json::is_default_mapbox_logo")
+    )]
     pub mapbox_logo: bool,
 
-    /// Optional. TODO: couldn't find proper description
-    #[serde(default = "default_format")]
-    pub format: String,
+    /// Optional. Default: "pbf". The format of the tiles, e.g. `png`. This is
+    /// an extension to the spec, kept as given so that it's written back.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub format: Option<String>,
 
     /// REQUIRED. Array.
     /// An array of objects. Each object describes one layer of vector tile data.
     /// A vector_layer object MUST contain the id and fields keys, and MAY contain the description, minzoom, or maxzoom keys.
-    #[serde(default)]
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Vec::is_empty"))]
     pub vector_layers: Vec<VectorLayer>,
+
+    /// Any top-level fields not described above, such as vendor-specific
+    /// extensions. They are kept as-is and written back on encode. Only
+    /// available with the `serde` feature.
+    #[cfg(feature = "serde")]
+    #[cfg_attr(feature = "serde", serde(flatten))]
+    pub other: Map<String, Value>,
+
+    /// Where `tile_url_rr` is in its rotation through `tiles`. Not part of
+    /// the document.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub(crate) endpoint_cursor: EndpointCursor,
 }
 
 impl Default for TileJson {
+    /// The spec defaults, with no tile endpoints. Since the spec requires at
+    /// least one, a default document fails `validate` with
+    /// `ValidationError::NoTiles` until `tiles` are added.
     fn default() -> Self {
         Self {
             tilejson: default_tilejson(),
+            id: Option::None,
             name: Option::None,
             description: Option::None,
             version: default_version(),
@@ -145,164 +244,2415 @@ impl Default for TileJson {
             data: vec![],
             minzoom: default_minzoom(),
             maxzoom: default_maxzoom(),
-            bounds: default_bounds(),
+            fillzoom: Option::None,
+            bounds: None,
             center: Option::None,
+            tile_size: Option::None,
             mapbox_logo: default_mapbox_logo(),
-            format: default_format(),
+            format: None,
             vector_layers: vec![],
+            #[cfg(feature = "serde")]
+            other: Map::new(),
+            endpoint_cursor: EndpointCursor::default(),
         }
     }
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub enum Scheme {
-    #[serde(rename = "xyz")]
-    XYZ,
-    #[serde(rename = "tms")]
-    TMS
+impl fmt::Debug for TileJson {
+    /// Lists the fields of the document, leaving out the `tile_url_rr`
+    /// rotation.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut debug = f.debug_struct("TileJson");
+        debug
+            .field("tilejson", &self.tilejson)
+            .field("id", &self.id)
+            .field("name", &self.name)
+            .field("description", &self.description)
+            .field("version", &self.version)
+            .field("attribution", &self.attribution)
+            .field("template", &self.template)
+            .field("legend", &self.legend)
+            .field("scheme", &self.scheme)
+            .field("tiles", &self.tiles)
+            .field("grids", &self.grids)
+            .field("data", &self.data)
+            .field("minzoom", &self.minzoom)
+            .field("maxzoom", &self.maxzoom)
+            .field("fillzoom", &self.fillzoom)
+            .field("bounds", &self.bounds)
+            .field("center", &self.center)
+            .field("tile_size", &self.tile_size)
+            .field("mapbox_logo", &self.mapbox_logo)
+            .field("format", &self.format)
+            .field("vector_layers", &self.vector_layers);
+        #[cfg(feature = "serde")]
+        debug.field("other", &self.other);
+        debug.finish()
+    }
 }
 
-impl Default for Scheme {
-    fn default() -> Self { Scheme::XYZ }
-}
+/// A counter shared by the callers of `TileJson::tile_url_rr`, so endpoints
+/// can be rotated through a `&TileJson`.
+///
+/// It holds no document data: all cursors compare equal and hash alike, and
+/// a clone starts from the position of the original.
+#[derive(Debug, Default)]
+pub(crate) struct EndpointCursor(AtomicUsize);
 
-fn default_tilejson() -> String {
-    "2.2.0".to_owned()
+impl Clone for EndpointCursor {
+    fn clone(&self) -> Self {
+        EndpointCursor(AtomicUsize::new(self.0.load(Ordering::Relaxed)))
+    }
 }
 
-fn default_version() -> String {
-    "1.0.0".to_owned()
+impl PartialEq for EndpointCursor {
+    fn eq(&self, _other: &Self) -> bool {
+        true
+    }
 }
 
-fn default_minzoom() -> u8 {
-    0
-}
+impl Eq for EndpointCursor {}
 
-fn default_maxzoom() -> u8 {
-    30
+impl Hash for EndpointCursor {
+    fn hash<H: Hasher>(&self, _state: &mut H) {}
 }
 
-fn default_bounds() -> Vec<f64> {
-    vec![-180.0, -90.0, 180.0, 90.0]
+/// The TileJSON specification a tileset declares in its `tilejson` field.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum SpecVersion {
+    /// TileJSON 2.2, any patch release.
+    V2_2,
+    /// TileJSON 3.0, any patch release.
+    V3_0,
+    /// Any other value, kept as written.
+    Other(String),
 }
 
-fn default_mapbox_logo() -> bool {
-    false
+/// A field that differs between two documents, as listed by
+/// `TileJson::diff`.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct FieldChange {
+    /// The name of the field in `TileJson`.
+    pub field: &'static str,
+    /// The `Debug` formatting of the field in the document `diff` was called on.
+    pub from: String,
+    /// The `Debug` formatting of the field in the other document.
+    pub to: String,
 }
 
-fn default_format() -> String {
-    "pbf".to_string()
+/// The shape of a tileset's `bounds` in web mercator space.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Orientation {
+    /// Wider than tall.
+    Landscape,
+    /// Taller than wide.
+    Portrait,
+    /// As wide as tall, give or take 1%.
+    Square,
 }
 
-pub fn decode(tilejson: &str) -> TileJson {
-    serde_json::from_str(tilejson).unwrap()
+/// Controls how `TileJson::merge_with` resolves conflicts.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub struct MergePolicy {
+    /// What to do when both documents carry the same key in `other` with
+    /// different values.
+    pub extras: ExtrasPolicy,
 }
 
-pub fn encode(tilejson: &TileJson) -> String {
-    serde_json::to_string(tilejson).unwrap()
+/// Resolution of a key present in both documents' `other` extras.
+#[derive(Debug, PartialEq, Clone, Copy, Default)]
+pub enum ExtrasPolicy {
+    /// Keep the value of the document being merged into.
+    PreferSelf,
+    /// Take the value of the document being merged in.
+    #[default]
+    PreferOther,
+    /// Fail the merge with `ValidationError::ConflictingExtra`.
+    Error,
 }
 
-#[derive(Serialize, Deserialize, Debug, PartialEq, Clone)]
-pub struct VectorLayer {
-    /// REQUIRED. String.
-    /// A string value representing the the layer id. For added context, this is referred to as
-    /// the name of the layer in the https://github.com/mapbox/vector-tile-spec/tree/master/2.1#41-layers
-    pub id: String,
+impl TileJson {
+    /// The `tilejson` spec version of default documents.
+    pub const DEFAULT_TILEJSON: &'static str = "2.2.0";
 
-    /// REQUIRED. Object.
-    /// An object whose keys and values are the names and descriptions of attributes available in this layer.
-    /// Each value (description) MUST be a string that describes the underlying data.
-    /// If no fields are present, the fields key MUST be an empty object.
-    #[serde(default, skip_serializing_if = "HashMap::is_empty")]
-    pub fields: HashMap<String, String>,
+    /// The `version` the spec assumes when it's missing.
+    pub const DEFAULT_VERSION: &'static str = "1.0.0";
 
-    /// OPTIONAL. String.
-    /// A string representing a human-readable description of the entire layer's contents.
-    #[serde(skip_serializing_if = "Option::is_none")]
-    pub description: Option<String>,
+    /// The `minzoom` the spec assumes when it's missing.
+    pub const DEFAULT_MINZOOM: u8 = 0;
 
-    /// OPTIONAL. Integer.
-    /// An integer representing the lowest/highest zoom level whose tiles this layer appears in.
-    /// minzoom MUST be greater than or equal to the set of tiles' minzoom.
-    #[serde(default)]
-    pub minzoom: u16,
+    /// The `maxzoom` the spec assumes when it's missing.
+    pub const DEFAULT_MAXZOOM: u8 = 30;
 
-    /// OPTIONAL. Integer.
-    /// An integer representing the lowest/highest zoom level whose tiles this layer appears in.
-    /// maxzoom MUST be less than or equal to the set of tiles' maxzoom.
-    #[serde(default)]
-    pub maxzoom: u16,
-}
+    /// The `bounds` the spec assumes when they're missing: the whole world.
+    pub const DEFAULT_BOUNDS: Bounds = bounds::WORLD;
 
-#[cfg(test)]
-mod tests {
-    use super::*;
+    /// Creates a document serving `tiles`, with every other field at its
+    /// default.
+    pub fn new(tiles: Vec<String>) -> Self {
+        TileJson { tiles, ..TileJson::default() }
+    }
 
-    #[test]
-    fn test_encode_default() {
-        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":[],"minzoom":0,"maxzoom":30,"bounds":[-180.0,-90.0,180.0,90.0]}"#;
-        let tilejson = TileJson::default();
-        assert_eq!(encode(&tilejson), encoded_str);
+    /// Creates a global XYZ tileset served from `tile_url`, with world
+    /// `bounds` and zoom levels 0 to 19, as a starting point for
+    /// prototypes and tests.
+    pub fn world(tile_url: impl Into<String>) -> TileJson {
+        TileJson {
+            tiles: vec![tile_url.into()],
+            scheme: Scheme::XYZ,
+            minzoom: 0,
+            maxzoom: 19,
+            bounds: Some(TileJson::DEFAULT_BOUNDS),
+            ..TileJson::default()
+        }
     }
 
-    #[test]
-    fn test_decode_default() {
-        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":[],"minzoom":0,"maxzoom":30,"bounds":[-180.0,-90.0,180.0,90.0]}"#;
-        let tilejson = TileJson::default();
-        assert_eq!(decode(&encoded_str), tilejson);
+    /// Starts building a document from the defaults.
+    pub fn builder() -> TileJsonBuilder {
+        TileJsonBuilder::new()
     }
 
-    #[test]
-    fn test_encode_example() {
-        let encoded_str = r#"{"tilejson":"1.0.0","name":"OpenStreetMap","description":"A free editable map of the whole world.","version":"1.0.0","attribution":"(c) OpenStreetMap contributors, CC-BY-SA","scheme":"xyz","tiles":["https://a.tile.openstreetmap.org/{z}/{x}/{y}.png","https://b.tile.openstreetmap.org/{z}/{x}/{y}.png","https://c.tile.openstreetmap.org/{z}/{x}/{y}.png"],"minzoom":0,"maxzoom":18,"bounds":[-180.0,-85.0,180.0,85.0]}"#;
-        let mut tilejson = TileJson::default();
-        tilejson.tilejson = "1.0.0".to_owned();
-        tilejson.name = Some("OpenStreetMap".to_owned());
-        tilejson.description = Some("A free editable map of the whole world.".to_owned());
-        tilejson.attribution = Some("(c) OpenStreetMap contributors, CC-BY-SA".to_owned());
-        tilejson.tiles = vec![
-            "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
-            "https://b.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
-            "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned()
+    /// Appends `url` to `tiles`, provided it has the `{z}`, `{x}` and `{y}`
+    /// (or `{-y}`) placeholders, or a `{quadkey}` (or `{q}`) one.
+    ///
+    /// Fails with `ValidationError::MissingPlaceholder`, leaving `tiles`
+    /// untouched, otherwise.
+    pub fn add_tile_endpoint(&mut self, url: impl Into<String>) -> Result<(), ValidationError> {
+        let url = url.into();
+        if !is_tile_endpoint(&url) {
+            return Err(ValidationError::MissingPlaceholder { url });
+        }
+        self.tiles.push(url);
+        Ok(())
+    }
+
+    /// Replaces `tiles` with `tiles`, provided every endpoint has the
+    /// placeholders `add_tile_endpoint` asks for, in any case.
+    ///
+    /// Fails with `ValidationError::MissingPlaceholder` for the first
+    /// endpoint lacking them, leaving the current `tiles` untouched.
+    pub fn set_tiles(&mut self, tiles: Vec<String>) -> Result<(), ValidationError> {
+        if let Some(url) = tiles.iter().find(|url| !is_tile_endpoint(url)) {
+            return Err(ValidationError::MissingPlaceholder { url: url.clone() });
+        }
+        self.tiles = tiles;
+        Ok(())
+    }
+
+    /// Overlays `other` onto this document, using the default `MergePolicy`.
+    ///
+    /// Fields are taken from `other` as follows:
+    /// - `Option` fields replace ours when `other`'s is `Some`;
+    /// - `tiles`, `grids`, `data` and `vector_layers` replace ours when
+    ///   `other`'s are non-empty;
+    /// - all other fields replace ours when `other`'s differ from the default;
+    /// - `other` extras are combined key by key, `other`'s value winning on
+    ///   conflicts.
+    pub fn merge(&mut self, other: &TileJson) {
+        self.merge_with(other, &MergePolicy::default())
+            .expect("the default merge policy never fails");
+    }
+
+    /// Overlays `other` onto this document like `merge`, resolving keys found
+    /// in both documents' extras according to `policy`.
+    ///
+    /// On error this document is left unchanged.
+    #[cfg_attr(not(feature = "serde"), allow(unused_variables))]
+    pub fn merge_with(&mut self, other: &TileJson, policy: &MergePolicy) -> Result<(), ValidationError> {
+        #[cfg(feature = "serde")]
+        if policy.extras == ExtrasPolicy::Error {
+            for (key, value) in &other.other {
+                if self.other.get(key).is_some_and(|ours| ours != value) {
+                    return Err(ValidationError::ConflictingExtra { key: key.clone() });
+                }
+            }
+        }
+
+        let defaults = TileJson::default();
+        merge_scalar(&mut self.tilejson, &other.tilejson, &defaults.tilejson);
+        merge_option(&mut self.id, &other.id);
+        merge_option(&mut self.name, &other.name);
+        merge_option(&mut self.description, &other.description);
+        merge_scalar(&mut self.version, &other.version, &defaults.version);
+        merge_option(&mut self.attribution, &other.attribution);
+        merge_option(&mut self.template, &other.template);
+        merge_option(&mut self.legend, &other.legend);
+        merge_scalar(&mut self.scheme, &other.scheme, &defaults.scheme);
+        merge_vec(&mut self.tiles, &other.tiles);
+        merge_vec(&mut self.grids, &other.grids);
+        merge_vec(&mut self.data, &other.data);
+        merge_scalar(&mut self.minzoom, &other.minzoom, &defaults.minzoom);
+        merge_scalar(&mut self.maxzoom, &other.maxzoom, &defaults.maxzoom);
+        merge_option(&mut self.fillzoom, &other.fillzoom);
+        merge_option(&mut self.bounds, &other.bounds);
+        merge_option(&mut self.center, &other.center);
+        merge_option(&mut self.tile_size, &other.tile_size);
+        merge_scalar(&mut self.mapbox_logo, &other.mapbox_logo, &defaults.mapbox_logo);
+        merge_scalar(&mut self.format, &other.format, &defaults.format);
+        merge_vec(&mut self.vector_layers, &other.vector_layers);
+
+        #[cfg(feature = "serde")]
+        for (key, value) in &other.other {
+            if policy.extras == ExtrasPolicy::PreferSelf && self.other.contains_key(key) {
+                continue;
+            }
+            self.other.insert(key.clone(), value.clone());
+        }
+        Ok(())
+    }
+
+    /// Whether this document equals `other`, taking the coordinates of
+    /// `bounds` and `center` as equal when they differ by at most `epsilon`
+    /// and comparing every other field exactly, like `==`.
+    pub fn approx_eq(&self, other: &TileJson, epsilon: f64) -> bool {
+        let close = |a: f64, b: f64| (a - b).abs() <= epsilon;
+        let bounds_close = match (self.bounds, other.bounds) {
+            (Some(ours), Some(theirs)) => <[f64; 4]>::from(ours)
+                .iter()
+                .zip(&<[f64; 4]>::from(theirs))
+                .all(|(&a, &b)| close(a, b)),
+            (ours, theirs) => ours.is_none() && theirs.is_none(),
+        };
+        let center_close = match (self.center, other.center) {
+            (Some(ours), Some(theirs)) => {
+                ours.zoom == theirs.zoom
+                    && close(ours.longitude, theirs.longitude)
+                    && close(ours.latitude, theirs.latitude)
+            }
+            (ours, theirs) => ours.is_none() && theirs.is_none(),
+        };
+        bounds_close
+            && center_close
+            && TileJson { bounds: other.bounds, center: other.center, ..self.clone() } == *other
+    }
+
+    /// Lists the optional fields without a default that are set, i.e. the
+    /// `Some` options and non-empty lists, in declaration order.
+    pub fn populated_fields(&self) -> Vec<&'static str> {
+        let fields = [
+            ("id", self.id.is_some()),
+            ("name", self.name.is_some()),
+            ("description", self.description.is_some()),
+            ("attribution", self.attribution.is_some()),
+            ("template", self.template.is_some()),
+            ("legend", self.legend.is_some()),
+            ("grids", !self.grids.is_empty()),
+            ("data", !self.data.is_empty()),
+            ("fillzoom", self.fillzoom.is_some()),
+            ("center", self.center.is_some()),
+            ("tile_size", self.tile_size.is_some()),
+            ("vector_layers", !self.vector_layers.is_empty()),
         ];
-        tilejson.maxzoom = 18;
-        tilejson.bounds = vec![ -180.0, -85.0, 180.0, 85.0 ];
+        fields.iter().filter(|(_, populated)| *populated).map(|(field, _)| *field).collect()
+    }
 
-        assert_eq!(encode(&tilejson), encoded_str);
+    /// Lists the fields that differ between this document and `other`, in
+    /// declaration order, with both values formatted with `Debug`.
+    pub fn diff(&self, other: &TileJson) -> Vec<FieldChange> {
+        let mut changes = Vec::new();
+        push_change(&mut changes, "tilejson", &self.tilejson, &other.tilejson);
+        push_change(&mut changes, "id", &self.id, &other.id);
+        push_change(&mut changes, "name", &self.name, &other.name);
+        push_change(&mut changes, "description", &self.description, &other.description);
+        push_change(&mut changes, "version", &self.version, &other.version);
+        push_change(&mut changes, "attribution", &self.attribution, &other.attribution);
+        push_change(&mut changes, "template", &self.template, &other.template);
+        push_change(&mut changes, "legend", &self.legend, &other.legend);
+        push_change(&mut changes, "scheme", &self.scheme, &other.scheme);
+        push_change(&mut changes, "tiles", &self.tiles, &other.tiles);
+        push_change(&mut changes, "grids", &self.grids, &other.grids);
+        push_change(&mut changes, "data", &self.data, &other.data);
+        push_change(&mut changes, "minzoom", &self.minzoom, &other.minzoom);
+        push_change(&mut changes, "maxzoom", &self.maxzoom, &other.maxzoom);
+        push_change(&mut changes, "fillzoom", &self.fillzoom, &other.fillzoom);
+        push_change(&mut changes, "bounds", &self.bounds, &other.bounds);
+        push_change(&mut changes, "center", &self.center, &other.center);
+        push_change(&mut changes, "tile_size", &self.tile_size, &other.tile_size);
+        push_change(&mut changes, "mapbox_logo", &self.mapbox_logo, &other.mapbox_logo);
+        push_change(&mut changes, "format", &self.format, &other.format);
+        push_change(&mut changes, "vector_layers", &self.vector_layers, &other.vector_layers);
+        #[cfg(feature = "serde")]
+        push_change(&mut changes, "other", &self.other, &other.other);
+        changes
     }
 
-    #[test]
-    fn test_decode_example() {
-        let encoded_str = r#"{
-            "tilejson": "1.0.0",
-            "name": "OpenStreetMap",
-            "description": "A free editable map of the whole world.",
-            "version": "1.0.0",
-            "attribution": "(c) OpenStreetMap contributors, CC-BY-SA",
-            "scheme": "xyz",
-            "tiles": [
-                "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png",
-                "https://b.tile.openstreetmap.org/{z}/{x}/{y}.png",
-                "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png"
-            ],
-            "minzoom": 0,
-            "maxzoom": 18,
-            "bounds": [ -180, -85, 180, 85 ]
-        }"#;
+    /// Drops the UTFGrid interactivity fields: `grids`, `template` and
+    /// `legend`.
+    pub fn strip_interactivity(&mut self) {
+        self.grids.clear();
+        self.template = None;
+        self.legend = None;
+    }
 
-        let mut tilejson = TileJson::default();
-        tilejson.tilejson = "1.0.0".to_owned();
-        tilejson.name = Some("OpenStreetMap".to_owned());
-        tilejson.description = Some("A free editable map of the whole world.".to_owned());
-        tilejson.attribution = Some("(c) OpenStreetMap contributors, CC-BY-SA".to_owned());
-        tilejson.tiles = vec![
-            "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
-            "https://b.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
-            "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned()
+    /// Drops the GeoJSON overlays listed in `data`.
+    pub fn strip_data(&mut self) {
+        self.data.clear();
+    }
+
+    /// Returns the names of the grid data keys the mustache `template`
+    /// references, in order of first appearance. Empty when there's no
+    /// `template`.
+    pub fn template_variables(&self) -> Vec<String> {
+        self.template.as_deref().map(mustache::variables).unwrap_or_default()
+    }
+
+    /// Like `merge_with`, but also checks that the merged document passes
+    /// `validate`. On error this document is left unchanged, so a merge never
+    /// silently produces an invalid document.
+    pub fn merge_validated(&mut self, other: &TileJson, policy: &MergePolicy) -> Result<(), ValidationError> {
+        let mut merged = self.clone();
+        merged.merge_with(other, policy)?;
+        merged.validate()?;
+        *self = merged;
+        Ok(())
+    }
+
+    /// Returns the URL of the XYZ tile `z`/`x`/`y` on the first endpoint of
+    /// `tiles`, converting `y` to TMS numbering for `{y}` when `scheme` says
+    /// so. A `{-y}` placeholder always takes the TMS row, whatever `scheme`
+    /// says, and `{quadkey}` or `{q}` the Bing Maps quadkey. Placeholders are
+    /// recognized regardless of case, so `{Z}` works like `{z}`.
+    ///
+    /// Returns `None` if there are no tile endpoints, or if `z` is above 30
+    /// or `x` or `y` is outside the `2^z` tiles of that zoom.
+    pub fn tile_url(&self, z: u32, x: u32, y: u32) -> Option<String> {
+        if !mercator::is_tile(z, x, y) {
+            return None;
+        }
+        let template = UrlTemplate::parse(self.tiles.first()?);
+        self.expand(&template, z, x, y)
+    }
+
+    /// Like `tile_url`, but takes the endpoints of `tiles` in turn on
+    /// successive calls to spread the load across them.
+    ///
+    /// Returns `None` if there are no tile endpoints or `z`/`x`/`y` isn't a
+    /// tile.
+    pub fn tile_url_rr(&self, z: u32, x: u32, y: u32) -> Option<String> {
+        if self.tiles.is_empty() || !mercator::is_tile(z, x, y) {
+            return None;
+        }
+        let turn = self.endpoint_cursor.0.fetch_add(1, Ordering::Relaxed);
+        let template = UrlTemplate::parse(&self.tiles[turn % self.tiles.len()]);
+        self.expand(&template, z, x, y)
+    }
+
+    /// Lazily yields the URL, on the first endpoint of `tiles`, of every
+    /// tile from `minzoom` to `maxzoom` covering `bounds`, or only those
+    /// covering the `[left, bottom, right, top]` box clipped to `bounds` when
+    /// `bbox` is given. Tiles are numbered according to `scheme`, and either
+    /// box may cross the antimeridian. The endpoint is parsed once up front.
+    ///
+    /// Yields nothing if there are no tile endpoints or `bounds` is
+    /// degenerate.
+    pub fn tile_url_pyramid(&self, bbox: Option<[f64; 4]>) -> impl Iterator<Item = String> + '_ {
+        let template = self.tiles.first().map(|url| UrlTemplate::parse(url));
+        template.into_iter().flat_map(move |template| {
+            self.pyramid_tiles(bbox).filter_map(move |(z, x, y)| self.expand(&template, z, x, y))
+        })
+    }
+
+    /// Lazily yields the URL, on the first endpoint of `tiles`, of every
+    /// tile covering `bounds` at zoom `z`, in the order of `tiles_at_zoom`.
+    /// Tiles are numbered according to `scheme`.
+    ///
+    /// Yields nothing if there are no tile endpoints or `bounds` is
+    /// degenerate.
+    pub fn tile_urls_for_zoom(&self, z: u32) -> impl Iterator<Item = String> + '_ {
+        let z = z.min(mercator::MAX_ZOOM);
+        let template = self.tiles.first().map(|url| UrlTemplate::parse(url));
+        let tiles = self.bounds_extent().map(|bounds| TileExtent::new(bounds, z).tiles());
+        template
+            .zip(tiles)
+            .into_iter()
+            .flat_map(move |(template, tiles)| tiles.filter_map(move |(x, y)| self.expand(&template, z, x, y)))
+    }
+
+    /// Returns the number of tiles yielded by `tile_url_pyramid` for the
+    /// same `bbox`, whether or not there are tile endpoints.
+    pub fn tile_count(&self, bbox: Option<[f64; 4]>) -> u64 {
+        match bbox {
+            Some(_) => self.pyramid_tiles(bbox).count() as u64,
+            None => self.tile_extents().map(|extent| extent.count()).sum(),
+        }
+    }
+
+    /// Switches `scheme` to `target`.
+    ///
+    /// The endpoints are left untouched: the row flip depends on the tile, so
+    /// it can't be written into a URL template. `tile_url` and friends apply
+    /// it to `{y}` when expanding, while `{-y}` is always flipped and so
+    /// comes out the same under either scheme.
+    pub fn to_scheme(&mut self, target: Scheme) {
+        self.scheme = target;
+    }
+
+    /// Rewrites the ArcGIS style `{level}`, `{col}` and `{row}` placeholders
+    /// in `tiles`, `grids` and `data` into `{z}`, `{x}` and `{y}`, leaving
+    /// any other braces untouched.
+    pub fn normalize_placeholders(&mut self) {
+        for endpoints in [&mut self.tiles, &mut self.grids, &mut self.data] {
+            for endpoint in endpoints.iter_mut() {
+                *endpoint = unaliased_placeholders(endpoint);
+            }
+        }
+    }
+
+    /// Brings the document into a canonical form: placeholders in `tiles`,
+    /// `grids` and `data` are lowercased, then each list is sorted and rid
+    /// of duplicates. Other fields are left untouched, so documents that only
+    /// differ in endpoint order compare equal once canonicalized.
+    pub fn canonicalize(&mut self) {
+        for endpoints in [&mut self.tiles, &mut self.grids, &mut self.data] {
+            for endpoint in endpoints.iter_mut() {
+                *endpoint = canonical_placeholders(endpoint);
+            }
+            endpoints.sort();
+            endpoints.dedup();
+        }
+    }
+
+    /// Reports likely mistakes that don't make the document invalid:
+    /// - placeholders that aren't lowercase;
+    /// - a global tileset whose `minzoom` isn't 0.
+    pub fn validate_warnings(&self) -> Vec<ValidationWarning> {
+        let mut warnings = Vec::new();
+        for url in self.tiles.iter().chain(&self.grids).chain(&self.data) {
+            if canonical_placeholders(url) != *url {
+                warnings.push(ValidationWarning::NonCanonicalPlaceholder { url: url.clone() });
+            }
+        }
+        if self.is_global() && self.minzoom > 0 {
+            warnings.push(ValidationWarning::NonZeroMinzoomGlobal { minzoom: self.minzoom });
+        }
+        warnings
+    }
+
+    /// Checks that `attribution`, if any, fits in `max_len` characters and
+    /// contains no control characters, so it can be laid out in an
+    /// attribution control.
+    pub fn validate_attribution(&self, max_len: usize) -> Result<(), ValidationWarning> {
+        let attribution = match &self.attribution {
+            Some(attribution) => attribution,
+            None => return Ok(()),
+        };
+        let len = attribution.chars().count();
+        if len > max_len {
+            return Err(ValidationWarning::AttributionTooLong { len, max_len });
+        }
+        if attribution.chars().any(char::is_control) {
+            return Err(ValidationWarning::AttributionControlCharacters);
+        }
+        Ok(())
+    }
+
+    /// Checks that the `tilejson` spec version is exactly one of `supported`,
+    /// for consumers implementing only specific versions of the spec.
+    pub fn assert_supported_version(&self, supported: &[&str]) -> Result<(), ValidationError> {
+        if supported.contains(&self.tilejson.as_str()) {
+            return Ok(());
+        }
+        Err(ValidationError::UnsupportedVersion {
+            version: self.tilejson.clone(),
+            supported: supported.iter().map(|version| version.to_string()).collect(),
+        })
+    }
+
+    /// Swaps `minzoom` and `maxzoom` if they are in the wrong order.
+    pub fn normalize_zoom_range(&mut self) {
+        if self.minzoom > self.maxzoom {
+            std::mem::swap(&mut self.minzoom, &mut self.maxzoom);
+        }
+    }
+
+    /// Clamps the zoom level of `center`, if present, into
+    /// `minzoom..=maxzoom`, e.g. after merging a global center into a layer
+    /// with a narrower zoom range.
+    ///
+    /// Does nothing if `minzoom` is greater than `maxzoom`, since there is no
+    /// range to clamp into; `normalize_zoom_range` fixes that first.
+    pub fn clamp_center_zoom(&mut self) {
+        if self.minzoom > self.maxzoom {
+            return;
+        }
+        let (minzoom, maxzoom) = (self.minzoom, self.maxzoom);
+        if let Some(center) = &mut self.center {
+            center.zoom = center.zoom.map(|zoom| zoom.clamp(minzoom, maxzoom));
+        }
+    }
+
+    /// Checks the document against the constraints of the spec:
+    /// - `tilejson` and `version` are semver.org style version numbers;
+    /// - `tiles` has at least one endpoint;
+    /// - `minzoom` and `maxzoom` are between 0 and 30;
+    /// - `minzoom` is not greater than `maxzoom`;
+    /// - `fillzoom`, if present, lies within `minzoom..=maxzoom`;
+    /// - `bounds` describes a non-empty area;
+    /// - `center`, if present, passes `validate_center`.
+    pub fn validate(&self) -> Result<(), ValidationError> {
+        for (field, value) in [("tilejson", &self.tilejson), ("version", &self.version)] {
+            if !is_semver(value) {
+                return Err(ValidationError::InvalidVersion { field, value: value.clone() });
+            }
+        }
+        if self.tiles.is_empty() {
+            return Err(ValidationError::NoTiles);
+        }
+        let max_zoom = mercator::MAX_ZOOM as u8;
+        for (field, zoom) in [("minzoom", self.minzoom), ("maxzoom", self.maxzoom)] {
+            if zoom > max_zoom {
+                return Err(ValidationError::ZoomOutOfRange { field, zoom, max_zoom });
+            }
+        }
+        if self.minzoom > self.maxzoom {
+            return Err(ValidationError::InvalidZoomRange {
+                minzoom: self.minzoom,
+                maxzoom: self.maxzoom,
+            });
+        }
+        if let Some(fillzoom) = self.fillzoom {
+            if fillzoom < self.minzoom || fillzoom > self.maxzoom {
+                return Err(ValidationError::FillzoomOutOfRange {
+                    fillzoom,
+                    minzoom: self.minzoom,
+                    maxzoom: self.maxzoom,
+                });
+            }
+        }
+        self.validate_center()
+    }
+
+    /// Checks that `center`, if present, lies within `bounds` and that its
+    /// zoom level lies within `minzoom..=maxzoom`. Fails if `bounds` doesn't
+    /// describe a non-empty area.
+    pub fn validate_center(&self) -> Result<(), ValidationError> {
+        let bounds = self
+            .bounds_extent()
+            .ok_or(ValidationError::InvalidBounds { bounds: self.bounds_or_default() })?;
+        let Center { longitude, latitude, zoom } = match self.center {
+            Some(center) => center,
+            None => return Ok(()),
+        };
+        if !extent_contains(bounds, longitude, latitude) {
+            return Err(ValidationError::CenterOutOfBounds { longitude, latitude });
+        }
+        match zoom {
+            Some(zoom) if zoom < self.minzoom || zoom > self.maxzoom => Err(ValidationError::CenterZoomOutOfRange {
+                zoom,
+                minzoom: self.minzoom,
+                maxzoom: self.maxzoom,
+            }),
+            _ => Ok(()),
+        }
+    }
+
+    /// The specification version declared by `tilejson`. Only the major and
+    /// minor parts are compared, so `2.2.1` is still `SpecVersion::V2_2`.
+    pub fn spec_version(&self) -> SpecVersion {
+        let mut parts = self.tilejson.splitn(3, '.');
+        match (parts.next(), parts.next(), parts.next()) {
+            (Some("2"), Some("2"), Some(_)) => SpecVersion::V2_2,
+            (Some("3"), Some("0"), Some(_)) => SpecVersion::V3_0,
+            _ => SpecVersion::Other(self.tilejson.clone()),
+        }
+    }
+
+    /// The size of the tiles in pixels, 256 unless `tile_size` says otherwise.
+    pub fn tile_size(&self) -> u32 {
+        self.tile_size.unwrap_or(DEFAULT_TILE_SIZE)
+    }
+
+    /// The format of the tiles, `pbf` unless `format` says otherwise.
+    pub fn format(&self) -> &str {
+        self.format.as_deref().unwrap_or(DEFAULT_FORMAT)
+    }
+
+    /// The highest zoom level worth fetching tiles for: `fillzoom` when it's
+    /// set and doesn't exceed `maxzoom`, since clients overzoom the tiles of
+    /// `fillzoom` beyond it, and `maxzoom` otherwise.
+    pub fn effective_maxzoom(&self) -> u8 {
+        match self.fillzoom {
+            Some(fillzoom) if fillzoom <= self.maxzoom => fillzoom,
+            _ => self.maxzoom,
+        }
+    }
+
+    /// The name of the tileset, if any.
+    pub fn name(&self) -> Option<&str> {
+        self.name.as_deref()
+    }
+
+    /// The version of the tileset, `1.0.0` unless `version` says otherwise.
+    pub fn version(&self) -> &str {
+        &self.version
+    }
+
+    /// The tile numbering of the endpoints, `Scheme::XYZ` unless `scheme`
+    /// says otherwise.
+    pub fn scheme(&self) -> Scheme {
+        self.scheme.clone()
+    }
+
+    /// The extent of the tileset, the whole world unless `bounds` says
+    /// otherwise.
+    pub fn bounds(&self) -> Bounds {
+        self.bounds_or_default()
+    }
+
+    /// The declared `bounds`, or `DEFAULT_BOUNDS` (the whole world) if the
+    /// document doesn't declare any.
+    pub fn bounds_or_default(&self) -> Bounds {
+        self.bounds.unwrap_or(TileJson::DEFAULT_BOUNDS)
+    }
+
+    /// Appends `text` to `attribution`, separated from any earlier sources by
+    /// `" | "`. `attribution` stays a single string, as the spec requires.
+    pub fn push_attribution(&mut self, text: impl AsRef<str>) {
+        let text = text.as_ref();
+        match &mut self.attribution {
+            Some(attribution) if !attribution.is_empty() => {
+                attribution.push_str(ATTRIBUTION_SEPARATOR);
+                attribution.push_str(text);
+            }
+            _ => self.attribution = Some(text.to_owned()),
+        }
+    }
+
+    /// Splits `attribution` back into the sources joined by
+    /// `push_attribution`.
+    ///
+    /// Returns an empty list if there is no attribution.
+    pub fn attributions(&self) -> Vec<&str> {
+        match self.attribution.as_deref() {
+            Some(attribution) if !attribution.is_empty() => attribution.split(ATTRIBUTION_SEPARATOR).collect(),
+            _ => Vec::new(),
+        }
+    }
+
+    /// Whether the tileset serves vector tiles: it declares `vector_layers`
+    /// or one of its `tiles` endpoints ends in `.pbf` or `.mvt`.
+    pub fn is_vector(&self) -> bool {
+        !self.vector_layers.is_empty() || self.has_endpoint_extension(&VECTOR_FORMATS)
+    }
+
+    /// Whether the tileset serves image tiles: it isn't `is_vector` and one
+    /// of its `tiles` endpoints ends in `.png`, `.jpg`, `.jpeg` or `.webp`.
+    pub fn is_raster(&self) -> bool {
+        !self.is_vector() && self.has_endpoint_extension(&RASTER_FORMATS)
+    }
+
+    /// Whether one of the `tiles` endpoints ends in one of `formats`.
+    fn has_endpoint_extension(&self, formats: &[&str]) -> bool {
+        self.tiles.iter().filter_map(|url| endpoint_extension(url)).any(|format| formats.contains(&format))
+    }
+
+    /// Returns the most specific extent known to be covered by the tileset.
+    ///
+    /// This is the single source of truth for coverage. Today it equals
+    /// `bounds`, but it may narrow it down once extensions provide tighter
+    /// information.
+    pub fn tightest_bounds(&self) -> Bounds {
+        self.bounds_or_default()
+    }
+
+    /// Whether the point `lng`/`lat` lies within `bounds`, edges included.
+    /// Bounds with `left > right` wrap across the antimeridian.
+    pub fn contains(&self, lng: f64, lat: f64) -> bool {
+        extent_contains(self.bounds_or_default().into(), lng, lat)
+    }
+
+    /// Returns the area of `bounds` in square degrees, measured the long way
+    /// round across the antimeridian when `left > right`. Degenerate bounds
+    /// have no area.
+    pub fn bounds_area_deg2(&self) -> f64 {
+        match self.bounds_extent() {
+            Some([left, bottom, right, top]) => {
+                let width = if left > right { right + 360.0 - left } else { right - left };
+                width * (top - bottom)
+            }
+            None => 0.0,
+        }
+    }
+
+    /// Whether the `bounds` of both tilesets share some area; bounds that
+    /// merely touch don't. Either may cross the antimeridian.
+    pub fn overlaps(&self, other: &TileJson) -> bool {
+        match (self.bounds_extent(), other.bounds_extent()) {
+            (Some(ours), Some(theirs)) => extents_intersect(ours, theirs),
+            _ => false,
+        }
+    }
+
+    /// Whether `bounds` effectively cover the whole world: every longitude and
+    /// at least the latitudes from -85 to 85, which web mercator maps
+    /// commonly round their limit to.
+    pub fn is_global(&self) -> bool {
+        match self.bounds_extent() {
+            Some([left, bottom, right, top]) => {
+                right - left >= 360.0 && bottom <= -GLOBAL_LATITUDE && top >= GLOBAL_LATITUDE
+            }
+            None => false,
+        }
+    }
+
+    /// Returns `bounds` in global pixel coordinates at zoom `z`, keeping the
+    /// `[left, bottom, right, top]` order. Pixel rows grow southward, so
+    /// `bottom` is greater than `top`; at zoom 0 the world spans `tile_size`
+    /// pixels in each direction.
+    pub fn bounds_to_pixels(&self, z: u32) -> [f64; 4] {
+        let tile_size = f64::from(self.tile_size());
+        let Bounds { left, bottom, right, top } = self.bounds_or_default();
+        let (left, top) = mercator::lonlat_to_tile_fraction(left, top, z);
+        let (right, bottom) = mercator::lonlat_to_tile_fraction(right, bottom, z);
+        [left * tile_size, bottom * tile_size, right * tile_size, top * tile_size]
+    }
+
+    /// Returns the width of `bounds` divided by its height, both measured in
+    /// web mercator space so that high-latitude tilesets come out as tall as
+    /// they're displayed. Degenerate bounds are treated as the whole world.
+    pub fn aspect_ratio(&self) -> f64 {
+        let [left, bottom, right, top] = self.bounds_extent().unwrap_or([-180.0, -90.0, 180.0, 90.0]);
+        let (left, top) = mercator::lonlat_to_tile_fraction(left, top, 0);
+        let (right, bottom) = mercator::lonlat_to_tile_fraction(right, bottom, 0);
+        let width = if left > right { right + 1.0 - left } else { right - left };
+        width / (bottom - top)
+    }
+
+    /// Classifies `aspect_ratio`, with ratios within 1% of 1 being square.
+    pub fn orientation(&self) -> Orientation {
+        let ratio = self.aspect_ratio();
+        if ratio > 1.01 {
+            Orientation::Landscape
+        } else if ratio < 1.0 / 1.01 {
+            Orientation::Portrait
+        } else {
+            Orientation::Square
+        }
+    }
+
+    /// Derives a center for documents without one: the middle of `bounds`,
+    /// across the antimeridian when `left > right`, at the zoom level halfway
+    /// between `minzoom` and `maxzoom`, rounded down.
+    pub fn compute_center(&self) -> Center {
+        let Bounds { left, bottom, right, top } = self.bounds_or_default();
+        let width = if left > right { right + 360.0 - left } else { right - left };
+        Center {
+            longitude: wrap_longitude(left + width / 2.0),
+            latitude: (bottom + top) / 2.0,
+            zoom: Some(((u16::from(self.minzoom) + u16::from(self.maxzoom)) / 2) as u8),
+        }
+    }
+
+    /// Fills `center` from `compute_center` if it isn't set.
+    pub fn ensure_center(&mut self) {
+        if self.center.is_none() {
+            self.center = Some(self.compute_center());
+        }
+    }
+
+    /// Returns the tile `(z, x, y)` containing the tileset center, numbered
+    /// according to `scheme`. The explicit `center` is used when present,
+    /// otherwise the middle of `bounds` at the midpoint between `minzoom` and
+    /// `maxzoom`. The resulting tile always lies inside `bounds`.
+    ///
+    /// Returns `None` if `bounds` is degenerate.
+    pub fn center_tile(&self) -> Option<(u32, u32, u32)> {
+        let (lon, lat, z) = self.effective_center()?;
+        let (x, y) = mercator::lonlat_to_tile(lon, lat, z);
+        Some((z, x, self.scheme_y(z, y)?))
+    }
+
+    /// Returns the WebMercatorQuad scale denominator of every zoom level from
+    /// `minzoom` to `maxzoom`, as `(zoom, scale_denominator)` pairs.
+    pub fn scale_denominators(&self) -> Vec<(u32, f64)> {
+        (u32::from(self.minzoom)..=u32::from(self.maxzoom))
+            .map(|z| (z, mercator::scale_denominator(z)))
+            .collect()
+    }
+
+    /// Returns the number of tiles covering `bounds` at every zoom level from
+    /// `minzoom` to `maxzoom`, as `(zoom, count)` pairs. Bounds crossing the
+    /// antimeridian are handled, and bounds beyond the world or the mercator
+    /// latitude limit are clamped.
+    ///
+    /// Returns an empty list if `bounds` is degenerate.
+    pub fn expected_tile_count_per_zoom(&self) -> Vec<(u32, u64)> {
+        self.tile_extents().map(|extent| (extent.z, extent.count())).collect()
+    }
+
+    /// Returns every tile `(z, x, y)` at `minzoom` covering `bounds`, numbered
+    /// according to `scheme`. Stitched together they make an overview of the
+    /// whole tileset.
+    ///
+    /// Returns an empty list if `bounds` is degenerate.
+    pub fn overview_tiles(&self) -> Vec<(u32, u32, u32)> {
+        self.tiles_at_zoom(u32::from(self.minzoom)).collect()
+    }
+
+    /// Lazily yields every tile `(z, x, y)` covering `bounds` at zoom `z`,
+    /// numbered according to `scheme`. Bounds crossing the antimeridian are
+    /// handled, and bounds beyond the world or the mercator latitude limit
+    /// are clamped.
+    ///
+    /// Yields nothing if `bounds` is degenerate.
+    pub fn tiles_at_zoom(&self, z: u32) -> impl Iterator<Item = (u32, u32, u32)> + '_ {
+        let z = z.min(mercator::MAX_ZOOM);
+        self.bounds_extent()
+            .map(|bounds| TileExtent::new(bounds, z))
+            .into_iter()
+            .flat_map(TileExtent::tiles)
+            .filter_map(move |(x, y)| Some((z, x, self.scheme_y(z, y)?)))
+    }
+
+    /// Returns the tiles `(z, x, y)` at zoom `z` covering the
+    /// `[left, bottom, right, top]` box, clipped to `bounds` and numbered
+    /// according to `scheme`. Either box may cross the antimeridian.
+    ///
+    /// Returns an empty list if `bounds` is degenerate.
+    pub fn tiles_for_bbox(&self, bbox: [f64; 4], z: u32) -> Vec<(u32, u32, u32)> {
+        self.tiles_for_bbox_buffered(bbox, z, 0)
+    }
+
+    /// Like `tiles_for_bbox`, but with a ring of `margin_tiles` extra tiles
+    /// added on every side of the box, e.g. to prefetch around a viewport.
+    /// The ring stops at the edges of the world and is clipped to `bounds`.
+    pub fn tiles_for_bbox_buffered(&self, bbox: [f64; 4], z: u32, margin_tiles: u32) -> Vec<(u32, u32, u32)> {
+        let z = z.min(mercator::MAX_ZOOM);
+        let bounds = match self.bounds_extent() {
+            Some(bounds) => bounds,
+            None => return vec![],
+        };
+        TileExtent::new(bbox, z)
+            .buffered(margin_tiles)
+            .tiles()
+            .filter(|&(x, y)| extents_intersect(mercator::tile_bbox(z, x, y), bounds))
+            .filter_map(|(x, y)| Some((z, x, self.scheme_y(z, y)?)))
+            .collect()
+    }
+
+    /// Fingerprints the tiles covering a region at zoom `z` together with
+    /// `version`, so a cached copy of the region can be checked for
+    /// staleness against a server-side value. The tiles are those of
+    /// `tiles_for_bbox`, and the fingerprint is a stable FNV-1a hash.
+    pub fn region_fingerprint(&self, bbox: [f64; 4], z: u32) -> u64 {
+        let mut hasher = Fnv1a::default();
+        for (z, x, y) in self.tiles_for_bbox(bbox, z) {
+            for coordinate in [z, x, y] {
+                hasher.write(&coordinate.to_le_bytes());
+            }
+        }
+        hasher.write(self.version.as_bytes());
+        hasher.finish()
+    }
+
+    /// Returns the tiles `(z, x, y)` at zoom `z` that intersect the circle of
+    /// great-circle radius `radius_km` around the `(longitude, latitude)`
+    /// point, numbered according to `scheme`. The circle is clipped to
+    /// `bounds` and the mercator latitude limit, and wraps across the
+    /// antimeridian.
+    ///
+    /// Whether a tile intersects the circle is decided from the distance to
+    /// its nearest corner or edge, so tiles barely grazing the circle may be
+    /// missed or included. Returns an empty list if `bounds` is degenerate.
+    pub fn tiles_within_radius(&self, center: (f64, f64), radius_km: f64, z: u32) -> Vec<(u32, u32, u32)> {
+        let (lon, lat) = center;
+        let z = z.min(mercator::MAX_ZOOM);
+        let bounds = match self.bounds_extent() {
+            Some(bounds) => bounds,
+            None => return vec![],
+        };
+        TileExtent::new(mercator::circle_bbox(lon, lat, radius_km), z)
+            .tiles()
+            .filter(|&(x, y)| {
+                let tile = mercator::tile_bbox(z, x, y);
+                extents_intersect(tile, bounds) && mercator::distance_to_bbox_km(lon, lat, tile) <= radius_km
+            })
+            .filter_map(|(x, y)| Some((z, x, self.scheme_y(z, y)?)))
+            .collect()
+    }
+
+    /// Lazily yields the tiles `(z, x, y)` covering `bounds` at the zoom
+    /// levels gained by raising the maximum zoom from `old_maxzoom` to
+    /// `maxzoom`, numbered according to `scheme`. Only zooms from `minzoom`
+    /// up are considered.
+    pub fn tiles_added_by_zoom_change(&self, old_maxzoom: u8) -> impl Iterator<Item = (u32, u32, u32)> + '_ {
+        let first_new = (u32::from(old_maxzoom) + 1).max(u32::from(self.minzoom));
+        let maxzoom = u32::from(self.maxzoom).min(mercator::MAX_ZOOM);
+        (first_new..=maxzoom).flat_map(move |z| self.tiles_at_zoom(z))
+    }
+
+    /// Checks that every absolute endpoint in `tiles`, `grids` and `data`
+    /// points at one of the `allowed` hosts (compared case-insensitively).
+    /// Relative endpoints and endpoints whose host is a bare `{token}` are
+    /// left to the client and always pass.
+    ///
+    /// Returns the first disallowed host found.
+    pub fn validate_hosts(&self, allowed: &[&str]) -> Result<(), ValidationError> {
+        let endpoints = self.tiles.iter().chain(&self.grids).chain(&self.data);
+        for url in endpoints {
+            let host = match endpoint_host(url) {
+                Some(host) => host,
+                None => continue,
+            };
+            if !allowed.iter().any(|allowed| allowed.eq_ignore_ascii_case(host)) {
+                return Err(ValidationError::DisallowedHost {
+                    url: url.clone(),
+                    host: host.to_owned(),
+                });
+            }
+        }
+        Ok(())
+    }
+
+    /// The `center` as `(longitude, latitude, zoom)`, falling back to the
+    /// middle of `bounds` and the midpoint zoom, clamped into `bounds` and
+    /// the zoom range.
+    fn effective_center(&self) -> Option<(f64, f64, u32)> {
+        let [left, bottom, right, top] = self.bounds_extent()?;
+        let minzoom = u32::from(self.minzoom).min(mercator::MAX_ZOOM);
+        let maxzoom = u32::from(self.maxzoom).clamp(minzoom, mercator::MAX_ZOOM);
+        let midzoom = (minzoom + maxzoom) / 2;
+
+        let (lon, lat, zoom) = match self.center {
+            Some(center) => (center.longitude, center.latitude, center.zoom.map_or(midzoom, u32::from)),
+            None => {
+                let center = self.compute_center();
+                (center.longitude, center.latitude, midzoom)
+            }
+        };
+
+        Some((
+            clamp_longitude(lon, left, right),
+            lat.clamp(bottom, top),
+            zoom.clamp(minzoom, maxzoom),
+        ))
+    }
+
+    /// The `bounds` as `[left, bottom, right, top]`, or `None` if they don't
+    /// describe a non-empty area.
+    fn bounds_extent(&self) -> Option<[f64; 4]> {
+        let extent = <[f64; 4]>::from(self.bounds_or_default());
+        let [left, bottom, right, top] = extent;
+        if extent.iter().all(|v| v.is_finite()) && left != right && bottom < top {
+            Some(extent)
+        } else {
+            None
+        }
+    }
+
+    /// The extent of `bounds` at every zoom level of the tileset.
+    fn tile_extents(&self) -> impl Iterator<Item = TileExtent> {
+        let bounds = self.bounds_extent();
+        let maxzoom = u32::from(self.maxzoom).min(mercator::MAX_ZOOM);
+        (u32::from(self.minzoom)..=maxzoom).filter_map(move |z| bounds.map(|bounds| TileExtent::new(bounds, z)))
+    }
+
+    /// The XYZ tiles `(z, x, y)` of every zoom level of the tileset covering
+    /// `bounds`, or covering `bbox` clipped to `bounds` when given.
+    fn pyramid_tiles(&self, bbox: Option<[f64; 4]>) -> impl Iterator<Item = (u32, u32, u32)> + '_ {
+        let bounds = self.bounds_extent();
+        let maxzoom = u32::from(self.maxzoom).min(mercator::MAX_ZOOM);
+        (u32::from(self.minzoom)..=maxzoom)
+            .flat_map(move |z| {
+                bounds.into_iter().flat_map(move |bounds| {
+                    TileExtent::new(bbox.unwrap_or(bounds), z)
+                        .tiles()
+                        .filter(move |&(x, y)| {
+                            bbox.is_none() || extents_intersect(mercator::tile_bbox(z, x, y), bounds)
+                        })
+                        .map(move |(x, y)| (z, x, y))
+                })
+            })
+    }
+
+    /// Expands `template` for the XYZ tile `z`/`x`/`y`, numbering `{y}`
+    /// according to `scheme`, or `None` if `y` isn't a row at zoom `z`.
+    fn expand(&self, template: &UrlTemplate, z: u32, x: u32, y: u32) -> Option<String> {
+        template.expand(z, x, y, self.scheme_y(z, y)?)
+    }
+
+    /// Converts an XYZ row at zoom `z` into the numbering used by `scheme`,
+    /// or `None` if a TMS row can't be flipped because `y` is out of range.
+    fn scheme_y(&self, z: u32, y: u32) -> Option<u32> {
+        match self.scheme {
+            Scheme::XYZ | Scheme::Other(_) => Some(y),
+            Scheme::TMS => mercator::flip_y(z, y),
+        }
+    }
+}
+
+fn push_change<T: PartialEq + fmt::Debug>(changes: &mut Vec<FieldChange>, field: &'static str, from: &T, to: &T) {
+    if from != to {
+        changes.push(FieldChange { field, from: format!("{:?}", from), to: format!("{:?}", to) });
+    }
+}
+
+fn merge_option<T: Clone>(ours: &mut Option<T>, theirs: &Option<T>) {
+    if theirs.is_some() {
+        ours.clone_from(theirs);
+    }
+}
+
+fn merge_vec<T: Clone>(ours: &mut Vec<T>, theirs: &[T]) {
+    if !theirs.is_empty() {
+        *ours = theirs.to_vec();
+    }
+}
+
+fn merge_scalar<T: Clone + PartialEq>(ours: &mut T, theirs: &T, default: &T) {
+    if theirs != default {
+        ours.clone_from(theirs);
+    }
+}
+
+/// The placeholders substituted in endpoints, in their canonical spelling.
+const PLACEHOLDERS: [&str; 6] = ["z", "x", "y", "-y", "quadkey", "q"];
+
+/// The ArcGIS style placeholders and the canonical ones they stand for.
+const PLACEHOLDER_ALIASES: [(&str, &str); 3] = [("level", "z"), ("col", "x"), ("row", "y")];
+
+/// The separator between the sources of an `attribution`.
+const ATTRIBUTION_SEPARATOR: &str = " | ";
+
+/// The latitude from which `bounds` count as reaching the poles.
+const GLOBAL_LATITUDE: f64 = 85.0;
+
+/// Lowercases the known placeholders of an endpoint, leaving any other
+/// braces untouched.
+fn canonical_placeholders(url: &str) -> String {
+    rewrite_placeholders(url, |name| {
+        PLACEHOLDERS.iter().copied().find(|placeholder| placeholder.eq_ignore_ascii_case(name))
+    })
+}
+
+/// Replaces the ArcGIS style placeholders of an endpoint with their
+/// canonical counterparts, leaving any other braces untouched.
+fn unaliased_placeholders(url: &str) -> String {
+    rewrite_placeholders(url, |name| {
+        PLACEHOLDER_ALIASES
+            .iter()
+            .find(|(alias, _)| alias.eq_ignore_ascii_case(name))
+            .map(|(_, placeholder)| *placeholder)
+    })
+}
+
+/// Replaces every `{name}` of an endpoint for which `rewrite` returns a new
+/// name, leaving the other braces untouched.
+fn rewrite_placeholders(url: &str, rewrite: impl Fn(&str) -> Option<&'static str>) -> String {
+    let mut rewritten = String::with_capacity(url.len());
+    let mut rest = url;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        let name = &rest[start + 1..end];
+        rewritten.push_str(&rest[..start]);
+        match rewrite(name) {
+            Some(placeholder) => {
+                rewritten.push('{');
+                rewritten.push_str(placeholder);
+                rewritten.push('}');
+            }
+            None => rewritten.push_str(&rest[start..=end]),
+        }
+        rest = &rest[end + 1..];
+    }
+    rewritten.push_str(rest);
+    rewritten
+}
+
+/// Whether `version` is a semver.org style `major.minor.patch` version, with
+/// optional `-pre.release` and `+build` suffixes.
+fn is_semver(version: &str) -> bool {
+    let is_number = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_digit()) && (part == "0" || !part.starts_with('0'))
+    };
+    let is_identifier = |part: &str| {
+        !part.is_empty() && part.bytes().all(|b| b.is_ascii_alphanumeric() || b == b'-')
+    };
+    let is_pre_identifier = |part: &str| {
+        is_identifier(part) && (is_number(part) || !part.bytes().all(|b| b.is_ascii_digit()))
+    };
+
+    let (version, build) = match version.split_once('+') {
+        Some((version, build)) => (version, Some(build)),
+        None => (version, None),
+    };
+    let (core, pre) = match version.split_once('-') {
+        Some((core, pre)) => (core, Some(pre)),
+        None => (version, None),
+    };
+    let core: Vec<&str> = core.split('.').collect();
+    core.len() == 3
+        && core.iter().all(|part| is_number(part))
+        && pre.is_none_or(|pre| pre.split('.').all(is_pre_identifier))
+        && build.is_none_or(|build| build.split('.').all(is_identifier))
+}
+
+/// Whether an endpoint contains the `{z}` and `{x}` placeholders, and `{y}`
+/// or `{-y}`, in any case, as `tile_url` recognizes them.
+fn has_xyz_placeholders(url: &str) -> bool {
+    let template = UrlTemplate::parse(url);
+    let has = |placeholder: Segment| template.segments().contains(&placeholder);
+    has(Segment::Z) && has(Segment::X) && (has(Segment::Y) || has(Segment::FlippedY))
+}
+
+/// Whether an endpoint contains the `{quadkey}` or `{q}` placeholder, in any
+/// case.
+fn has_quadkey_placeholder(url: &str) -> bool {
+    UrlTemplate::parse(url).segments().contains(&Segment::Quadkey)
+}
+
+/// Whether an endpoint can address tiles, through `{z}`/`{x}`/`{y}` or a
+/// quadkey.
+fn is_tile_endpoint(url: &str) -> bool {
+    has_xyz_placeholders(url) || has_quadkey_placeholder(url)
+}
+
+/// The tile formats recognized from endpoint extensions.
+const TILE_FORMATS: [&str; 6] = ["png", "jpg", "jpeg", "webp", "pbf", "mvt"];
+
+/// The `TILE_FORMATS` of vector tiles.
+const VECTOR_FORMATS: [&str; 2] = ["pbf", "mvt"];
+
+/// The `TILE_FORMATS` of image tiles.
+const RASTER_FORMATS: [&str; 4] = ["png", "jpg", "jpeg", "webp"];
+
+/// Returns the lowercased extension of an endpoint's path if it's one of
+/// `TILE_FORMATS`.
+fn endpoint_extension(url: &str) -> Option<&'static str> {
+    let path = url.split(['?', '#']).next()?;
+    let (_, extension) = path.rsplit_once('.')?;
+    TILE_FORMATS.iter().copied().find(|format| format.eq_ignore_ascii_case(extension))
+}
+
+/// Extracts the host of an absolute or protocol-relative endpoint, without
+/// user info or port. Returns `None` for relative endpoints and hosts that are
+/// a single `{token}`.
+fn endpoint_host(url: &str) -> Option<&str> {
+    let rest = match url.find("://") {
+        Some(pos) => &url[pos + 3..],
+        None => url.strip_prefix("//")?,
+    };
+    let authority = rest.split(['/', '?', '#']).next()?;
+    let host = authority.rsplit('@').next()?;
+    let host = match host.rfind(':') {
+        Some(pos) if !host.ends_with(']') => &host[..pos],
+        _ => host,
+    };
+    let is_token = host.starts_with('{') && host.ends_with('}') && host.matches('{').count() == 1;
+    if host.is_empty() || is_token {
+        None
+    } else {
+        Some(host)
+    }
+}
+
+/// Whether `[left, bottom, right, top]` contains the point, edges included.
+/// `left > right` means the box crosses the antimeridian.
+fn extent_contains([left, bottom, right, top]: [f64; 4], lon: f64, lat: f64) -> bool {
+    let lon_inside = if left <= right {
+        left <= lon && lon <= right
+    } else {
+        lon >= left || lon <= right
+    };
+    lon_inside && bottom <= lat && lat <= top
+}
+
+/// Whether two `[left, bottom, right, top]` boxes share some area. Either may
+/// cross the antimeridian.
+fn extents_intersect(a: [f64; 4], b: [f64; 4]) -> bool {
+    split_antimeridian(a).iter().flatten().any(|a| {
+        split_antimeridian(b)
+            .iter()
+            .flatten()
+            .any(|b| a[0] < b[2] && b[0] < a[2] && a[1] < b[3] && b[1] < a[3])
+    })
+}
+
+/// Splits a box crossing the antimeridian into its eastern and western
+/// parts.
+fn split_antimeridian(bbox: [f64; 4]) -> [Option<[f64; 4]>; 2] {
+    let [left, bottom, right, top] = bbox;
+    if left > right {
+        [Some([left, bottom, 180.0, top]), Some([-180.0, bottom, right, top])]
+    } else {
+        [Some(bbox), None]
+    }
+}
+
+/// Brings a longitude back into [-180, 180].
+fn wrap_longitude(lon: f64) -> f64 {
+    if lon > 180.0 { lon - 360.0 } else { lon }
+}
+
+/// Clamps a longitude into `left..=right`, where `left > right` means the
+/// range crosses the antimeridian.
+fn clamp_longitude(lon: f64, left: f64, right: f64) -> f64 {
+    if left <= right {
+        return lon.clamp(left, right);
+    }
+    if lon >= left || lon <= right {
+        return lon;
+    }
+    if left - lon < lon - right { left } else { right }
+}
+
+/// The tile numbering of the endpoints. Decoding matches `xyz` and `tms`
+/// regardless of case.
+#[derive(Debug, PartialEq, Eq, Hash, Clone, Default)]
+pub enum Scheme {
+    #[default]
+    XYZ,
+    TMS,
+    /// A scheme other than `xyz` or `tms`, kept as written so it survives a
+    /// round trip. Its tiles are addressed like `XYZ` ones.
+    Other(String),
+}
+
+impl Scheme {
+    /// The name of the scheme in TileJSON documents.
+    pub(crate) fn as_str(&self) -> &str {
+        match self {
+            Scheme::XYZ => "xyz",
+            Scheme::TMS => "tms",
+            Scheme::Other(scheme) => scheme,
+        }
+    }
+}
+
+impl fmt::Display for Scheme {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for Scheme {
+    type Err = ParseSchemeError;
+
+    /// Parses `xyz` or `tms`, regardless of case.
+    fn from_str(scheme: &str) -> Result<Self, Self::Err> {
+        match scheme.to_ascii_lowercase().as_str() {
+            "xyz" => Ok(Scheme::XYZ),
+            "tms" => Ok(Scheme::TMS),
+            _ => Err(ParseSchemeError { scheme: scheme.to_owned() }),
+        }
+    }
+}
+
+const DEFAULT_TILE_SIZE: u32 = 256;
+
+const DEFAULT_FORMAT: &str = "pbf";
+
+fn default_tilejson() -> String {
+    TileJson::DEFAULT_TILEJSON.to_owned()
+}
+
+fn default_version() -> String {
+    TileJson::DEFAULT_VERSION.to_owned()
+}
+
+fn default_minzoom() -> u8 {
+    TileJson::DEFAULT_MINZOOM
+}
+
+fn default_maxzoom() -> u8 {
+    TileJson::DEFAULT_MAXZOOM
+}
+
+fn default_mapbox_logo() -> bool {
+    false
+}
+
+/// Wraps a single XYZ tile URL in a document covering the whole world at the
+/// default zoom range, with `format` taken from the URL's extension when it's
+/// a known one.
+///
+/// Fails with `ValidationError::MissingPlaceholder` if the URL lacks any of
+/// the `{z}`, `{x}` and `{y}` placeholders, `{-y}` standing in for `{y}`.
+pub fn from_xyz_url(url: &str) -> Result<TileJson, Error> {
+    if !has_xyz_placeholders(url) {
+        return Err(ValidationError::MissingPlaceholder { url: url.to_owned() }.into());
+    }
+    let mut builder = TileJson::builder().tile(url);
+    if let Some(format) = endpoint_extension(url) {
+        builder = builder.format(format);
+    }
+    let tilejson = builder.build();
+    tilejson.validate()?;
+    Ok(tilejson)
+}
+
+#[cfg_attr(feature = "serde", derive(Serialize, Deserialize))]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct VectorLayer {
+    /// REQUIRED. String.
+    /// A string value representing the the layer id. For added context, this is referred to as
+    /// the name of the layer in the https://github.com/mapbox/vector-tile-spec/tree/master/2.1#41-layers
+    pub id: String,
+
+    /// REQUIRED. Object.
+    /// An object whose keys and values are the names and descriptions of attributes available in this layer.
+    /// Each value (description) MUST be a string that describes the underlying data.
+    /// If no fields are present, the fields key MUST be an empty object.
+    #[cfg_attr(feature = "serde", serde(default))]
+    pub fields: HashMap<String, String>,
+
+    /// OPTIONAL. String.
+    /// A string representing a human-readable description of the entire layer's contents.
+    #[cfg_attr(feature = "serde", serde(skip_serializing_if = "Option::is_none"))]
+    pub description: Option<String>,
+
+    /// OPTIONAL. Integer.
+    /// An integer representing the lowest/highest zoom level whose tiles this layer appears in.
+    /// minzoom MUST be greater than or equal to the set of tiles' minzoom.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub minzoom: Option<u8>,
+
+    /// OPTIONAL. Integer.
+    /// An integer representing the lowest/highest zoom level whose tiles this layer appears in.
+    /// maxzoom MUST be less than or equal to the set of tiles' maxzoom.
+    #[cfg_attr(feature = "serde", serde(default, skip_serializing_if = "Option::is_none"))]
+    pub maxzoom: Option<u8>,
+}
+
+impl Hash for VectorLayer {
+    /// Hashes `fields` in key order, so equal layers hash alike whatever
+    /// order their fields were inserted in.
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.id.hash(state);
+        let mut fields: Vec<_> = self.fields.iter().collect();
+        fields.sort_unstable();
+        fields.hash(state);
+        self.description.hash(state);
+        self.minzoom.hash(state);
+        self.maxzoom.hash(state);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use std::collections::HashSet;
+
+    pub(super) fn osm_example() -> TileJson {
+        TileJson {
+            tilejson: "1.0.0".to_owned(),
+            name: Some("OpenStreetMap".to_owned()),
+            description: Some("A free editable map of the whole world.".to_owned()),
+            attribution: Some("(c) OpenStreetMap contributors, CC-BY-SA".to_owned()),
+            tiles: vec![
+                "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+                "https://b.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+                "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned()
+            ],
+            maxzoom: 18,
+            bounds: Some(Bounds::from([ -180.0, -85.0, 180.0, 85.0 ])),
+            ..TileJson::default()
+        }
+    }
+
+    #[test]
+    fn test_diff() {
+        let cached = osm_example();
+        assert!(cached.diff(&cached.clone()).is_empty());
+
+        let fresh = TileJson { maxzoom: 19, attribution: Some("© OpenStreetMap".to_owned()), ..osm_example() };
+        let changes = cached.diff(&fresh);
+        assert_eq!(changes.len(), 2);
+        assert_eq!(changes.iter().map(|change| change.field).collect::<Vec<_>>(), vec!["attribution", "maxzoom"]);
+        assert_eq!(changes[1], FieldChange { field: "maxzoom", from: "18".to_owned(), to: "19".to_owned() });
+        assert_eq!(changes[0].to, r#"Some("© OpenStreetMap")"#);
+    }
+
+    #[test]
+    fn test_is_raster_and_is_vector() {
+        let osm = osm_example();
+        assert!(osm.is_raster());
+        assert!(!osm.is_vector());
+
+        let vector = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{y}".to_owned()],
+            vector_layers: vec![VectorLayer {
+                id: "water".to_owned(),
+                fields: HashMap::new(),
+                description: None,
+                minzoom: None,
+                maxzoom: None,
+            }],
+            ..TileJson::default()
+        };
+        assert!(vector.is_vector());
+        assert!(!vector.is_raster());
+
+        let mvt = TileJson::new(vec!["https://tiles.example.com/{z}/{x}/{y}.MVT?key=a".to_owned()]);
+        assert!(mvt.is_vector());
+        let unknown = TileJson::new(vec!["https://tiles.example.com/{z}/{x}/{y}".to_owned()]);
+        assert!(!unknown.is_vector() && !unknown.is_raster());
+    }
+
+    #[test]
+    fn test_add_tile_endpoint() {
+        let mut tilejson = TileJson::default();
+        assert_eq!(tilejson.add_tile_endpoint("https://tile.openstreetmap.org/{z}/{x}/{y}.png"), Ok(()));
+        assert_eq!(tilejson.add_tile_endpoint("https://ecn.t0.tiles.virtualearth.net/tiles/a{quadkey}.jpeg"), Ok(()));
+        assert_eq!(tilejson.tiles.len(), 2);
+
+        assert_eq!(
+            tilejson.add_tile_endpoint("https://x/tiles.png"),
+            Err(ValidationError::MissingPlaceholder { url: "https://x/tiles.png".to_owned() })
+        );
+        assert!(tilejson.add_tile_endpoint("https://x/{z}/{x}.png").is_err());
+        assert_eq!(tilejson.tiles.len(), 2);
+
+        assert_eq!(tilejson.add_tile_endpoint("https://a/{Z}/{X}/{Y}.png"), Ok(()));
+        assert_eq!(tilejson.add_tile_endpoint("https://b/{Z}/{X}/{-Y}.png"), Ok(()));
+        assert_eq!(tilejson.add_tile_endpoint("https://c/a{QuadKey}.jpeg"), Ok(()));
+        assert_eq!(tilejson.tiles.len(), 5);
+
+        let mut mixed_case = TileJson::default();
+        assert_eq!(mixed_case.add_tile_endpoint("https://a/{Z}/{X}/{Y}.png"), Ok(()));
+        assert_eq!(mixed_case.validate(), Ok(()));
+        assert_eq!(mixed_case.tile_url(1, 0, 1), Some("https://a/1/0/1.png".to_owned()));
+    }
+
+    #[test]
+    fn test_effective_maxzoom() {
+        let tilejson = TileJson { fillzoom: Some(14), maxzoom: 22, ..osm_example() };
+        assert_eq!(tilejson.effective_maxzoom(), 14);
+        assert_eq!(osm_example().effective_maxzoom(), 18);
+        let tilejson = TileJson { fillzoom: Some(20), ..osm_example() };
+        assert_eq!(tilejson.effective_maxzoom(), 18);
+    }
+
+    #[test]
+    fn test_world() {
+        let tilejson = TileJson::world("https://x/{z}/{x}/{y}.png");
+        assert_eq!(tilejson.validate(), Ok(()));
+        assert_eq!(tilejson.tiles, vec!["https://x/{z}/{x}/{y}.png".to_owned()]);
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 19));
+        assert_eq!(tilejson.bounds, Some(Bounds::default()));
+        assert_eq!(tilejson.scheme, Scheme::XYZ);
+        assert!(tilejson.is_global());
+    }
+
+    #[test]
+    fn test_set_tiles() {
+        let mut tilejson = osm_example();
+        let subdomains: Vec<String> =
+            ["a", "b", "c", "d"].iter().map(|s| format!("https://{}.tiles.example.com/{{z}}/{{x}}/{{y}}.png", s)).collect();
+        assert_eq!(tilejson.set_tiles(subdomains.clone()), Ok(()));
+        assert_eq!(tilejson.tiles, subdomains);
+
+        let original = osm_example().tiles;
+        let mut tilejson = osm_example();
+        let batch = vec![
+            "https://a.tiles.example.com/{z}/{x}/{y}.png".to_owned(),
+            "https://b.tiles.example.com/tiles.png".to_owned(),
+            "https://c.tiles.example.com/{z}/{x}/{y}.png".to_owned(),
+        ];
+        assert_eq!(
+            tilejson.set_tiles(batch),
+            Err(ValidationError::MissingPlaceholder { url: "https://b.tiles.example.com/tiles.png".to_owned() })
+        );
+        assert_eq!(tilejson.tiles, original);
+
+        let mixed_case = vec!["https://a/{Z}/{X}/{Y}.png".to_owned(), "https://b/{z}/{X}/{-y}.png".to_owned()];
+        assert_eq!(tilejson.set_tiles(mixed_case.clone()), Ok(()));
+        assert_eq!(tilejson.tiles, mixed_case);
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://a/2/1/0.png".to_owned()));
+    }
+
+    #[test]
+    fn test_from_xyz_url() {
+        let url = "https://tile.openstreetmap.org/{z}/{x}/{y}.png";
+        let tilejson = from_xyz_url(url).unwrap();
+        assert_eq!(tilejson.tiles, vec![url.to_owned()]);
+        assert_eq!(tilejson.format(), "png");
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 30));
+        assert_eq!(tilejson.bounds_or_default(), Bounds::default());
+
+        let err = from_xyz_url("https://tile.openstreetmap.org/{z}/{x}.png").unwrap_err();
+        assert!(matches!(
+            err,
+            Error::Validation(ValidationError::MissingPlaceholder { .. })
+        ));
+    }
+
+    fn assert_pixels_eq(actual: [f64; 4], expected: [f64; 4]) {
+        for (actual, expected) in actual.iter().zip(&expected) {
+            assert!((actual - expected).abs() < 1e-6, "{:?} != {:?}", actual, expected);
+        }
+    }
+
+    #[test]
+    fn test_tightest_bounds() {
+        assert_eq!(TileJson::default().tightest_bounds(), Bounds::default());
+        assert_eq!(osm_example().tightest_bounds(), Bounds::from([-180.0, -85.0, 180.0, 85.0]));
+    }
+
+    #[test]
+    fn test_bounds_to_pixels() {
+        let mut tilejson = TileJson::default();
+        assert_pixels_eq(tilejson.bounds_to_pixels(0), [0.0, 256.0, 256.0, 0.0]);
+        assert_pixels_eq(tilejson.bounds_to_pixels(1), [0.0, 512.0, 512.0, 0.0]);
+
+        tilejson.tile_size = Some(512);
+        assert_pixels_eq(tilejson.bounds_to_pixels(0), [0.0, 512.0, 512.0, 0.0]);
+
+        tilejson.bounds = Some(Bounds::from([ 0.0, -90.0, 90.0, 0.0 ]));
+        assert_pixels_eq(tilejson.bounds_to_pixels(2), [1024.0, 2048.0, 1536.0, 1024.0]);
+    }
+
+    #[test]
+    fn test_aspect_ratio_and_orientation() {
+        let mut tilejson = TileJson::default();
+        assert!((tilejson.aspect_ratio() - 1.0).abs() < 1e-9);
+        assert_eq!(tilejson.orientation(), Orientation::Square);
+
+        tilejson.bounds = Some(Bounds::from([ -120.0, 20.0, 60.0, 40.0 ]));
+        assert!(tilejson.aspect_ratio() > 1.0);
+        assert_eq!(tilejson.orientation(), Orientation::Landscape);
+
+        tilejson.bounds = Some(Bounds::from([ 170.0, 60.0, -170.0, 80.0 ]));
+        assert!((tilejson.aspect_ratio() - 0.3119).abs() < 1e-3);
+        assert_eq!(tilejson.orientation(), Orientation::Portrait);
+    }
+
+    #[test]
+    fn test_center_tile() {
+        let mut tilejson = osm_example();
+        tilejson.center = Some(Center { longitude: -122.4194, latitude: 37.7749, zoom: Some(10) });
+        assert_eq!(tilejson.center_tile(), Some((10, 163, 395)));
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(tilejson.center_tile(), Some((10, 163, 628)));
+
+        tilejson.scheme = Scheme::XYZ;
+        tilejson.center = None;
+        tilejson.bounds = Some(Bounds::from([ 0.0, 0.0, 90.0, 45.0 ]));
+        tilejson.minzoom = 0;
+        tilejson.maxzoom = 4;
+        assert_eq!(tilejson.center_tile(), Some((2, 2, 1)));
+
+        tilejson.bounds = Some(Bounds::from([ 10.0, 0.0, 10.0, 45.0 ]));
+        assert_eq!(tilejson.center_tile(), None);
+    }
+
+    #[test]
+    fn test_scale_denominators() {
+        let tilejson = TileJson { minzoom: 2, maxzoom: 4, ..TileJson::default() };
+        let zooms: Vec<u32> = tilejson.scale_denominators().iter().map(|(z, _)| *z).collect();
+        assert_eq!(zooms, vec![2, 3, 4]);
+        assert_eq!(tilejson.scale_denominators()[0].1, mercator::scale_denominator(2));
+    }
+
+    #[test]
+    fn test_merge() {
+        let mut tilejson = osm_example();
+        let overlay = TileJson {
+            maxzoom: 14,
+            attribution: Some("(c) Example".to_owned()),
+            ..TileJson::default()
+        };
+        tilejson.merge(&overlay);
+
+        assert_eq!(tilejson.maxzoom, 14);
+        assert_eq!(tilejson.attribution, Some("(c) Example".to_owned()));
+        assert_eq!(tilejson.tiles, osm_example().tiles);
+        assert_eq!(tilejson.name, osm_example().name);
+    }
+
+    #[test]
+    fn test_merge_keeps_fields_left_at_default() {
+        let mut tilejson = TileJson { minzoom: 4, scheme: Scheme::TMS, ..osm_example() };
+        tilejson.merge(&TileJson { maxzoom: 14, ..TileJson::default() });
+
+        assert_eq!(tilejson.minzoom, 4);
+        assert_eq!(tilejson.scheme, Scheme::TMS);
+        assert_eq!(tilejson.bounds, osm_example().bounds);
+        assert_eq!(tilejson.maxzoom, 14);
+    }
+
+    #[test]
+    fn test_expected_tile_count_per_zoom() {
+        let tilejson = TileJson {
+            minzoom: 1,
+            maxzoom: 3,
+            bounds: Some(Bounds::from([ 0.0, 0.0, 90.0, 45.0 ])),
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson.expected_tile_count_per_zoom(), vec![(1, 1), (2, 1), (3, 4)]);
+
+        let tilejson = TileJson { bounds: Some(Bounds::from([ 170.0, -10.0, -170.0, 10.0 ])), maxzoom: 2, ..tilejson };
+        assert_eq!(tilejson.expected_tile_count_per_zoom(), vec![(1, 4), (2, 4)]);
+
+        let tilejson = TileJson { bounds: Some(Bounds::from([ 0.0, 0.0, 0.0, 0.0 ])), ..tilejson };
+        assert_eq!(tilejson.expected_tile_count_per_zoom(), vec![]);
+    }
+
+    #[test]
+    fn test_overview_tiles() {
+        let tilejson = TileJson {
+            minzoom: 3,
+            bounds: Some(Bounds::from([ 0.0, 0.0, 90.0, 45.0 ])),
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson.overview_tiles(), vec![(3, 4, 2), (3, 4, 3), (3, 5, 2), (3, 5, 3)]);
+
+        let tilejson = TileJson { scheme: Scheme::TMS, ..tilejson };
+        assert_eq!(tilejson.overview_tiles(), vec![(3, 4, 5), (3, 4, 4), (3, 5, 5), (3, 5, 4)]);
+
+        let tilejson = TileJson { minzoom: 1, bounds: Some(Bounds::from([ 170.0, -10.0, -170.0, 10.0 ])), ..tilejson };
+        assert_eq!(tilejson.overview_tiles(), vec![(1, 1, 1), (1, 1, 0), (1, 0, 1), (1, 0, 0)]);
+    }
+
+    #[test]
+    fn test_tiles_for_bbox() {
+        let mut tilejson = TileJson::default();
+        assert_eq!(tilejson.tiles_for_bbox([0.0, 0.0, 90.0, 45.0], 2), vec![(2, 2, 1)]);
+        assert_eq!(tilejson.tiles_for_bbox([170.0, -10.0, -170.0, 10.0], 1).len(), 4);
+
+        tilejson.bounds = Some(Bounds::from([ 0.0, 0.0, 180.0, 85.0 ]));
+        assert_eq!(tilejson.tiles_for_bbox([-180.0, -85.0, 180.0, 85.0], 1), vec![(1, 1, 0)]);
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(tilejson.tiles_for_bbox([-180.0, -85.0, 180.0, 85.0], 1), vec![(1, 1, 1)]);
+    }
+
+    #[test]
+    fn test_tiles_for_bbox_buffered() {
+        let mut tilejson = TileJson::default();
+        let [west, south, east, north] = mercator::tile_bbox(10, 512, 511);
+        let bbox = [west + 0.01, south + 0.01, east - 0.01, north - 0.01];
+        assert_eq!(tilejson.tiles_for_bbox(bbox, 10), vec![(10, 512, 511)]);
+
+        let buffered = tilejson.tiles_for_bbox_buffered(bbox, 10, 1);
+        assert_eq!(buffered.len(), 9);
+        for x in 511..=513 {
+            for y in 510..=512 {
+                assert!(buffered.contains(&(10, x, y)));
+            }
+        }
+        assert_eq!(tilejson.tiles_for_bbox_buffered(bbox, 10, 2).len(), 25);
+
+        assert_eq!(tilejson.tiles_for_bbox_buffered([-179.9, 85.03, -179.8, 85.04], 10, 1).len(), 4);
+
+        tilejson.bounds = Some(Bounds::from([ 0.0, 0.0, 180.0, 85.0 ]));
+        assert_eq!(tilejson.tiles_for_bbox_buffered(bbox, 10, 1).len(), 4);
+    }
+
+    #[test]
+    fn test_tile_url_pyramid() {
+        let mut tilejson = TileJson { minzoom: 2, maxzoom: 4, ..osm_example() };
+        tilejson.tiles.truncate(1);
+        let bbox = Some([5.9, 45.8, 10.5, 47.8]);
+        let urls: Vec<String> = tilejson.tile_url_pyramid(bbox).collect();
+        assert_eq!(urls.len() as u64, tilejson.tile_count(bbox));
+        assert_eq!(
+            urls,
+            vec![
+                "https://a.tile.openstreetmap.org/2/2/1.png",
+                "https://a.tile.openstreetmap.org/3/4/2.png",
+                "https://a.tile.openstreetmap.org/4/8/5.png",
+            ]
+        );
+
+        let bbox = Some([170.0, -10.0, -170.0, 10.0]);
+        assert_eq!(tilejson.tile_url_pyramid(bbox).count(), 12);
+        assert_eq!(tilejson.tile_count(bbox), 12);
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(
+            tilejson.tile_url_pyramid(Some([5.9, 45.8, 10.5, 47.8])).next(),
+            Some("https://a.tile.openstreetmap.org/2/2/2.png".to_owned())
+        );
+        assert_eq!(tilejson.tile_url_pyramid(None).count() as u64, tilejson.tile_count(None));
+        assert_eq!(tilejson.tile_count(None), 16 + 64 + 256);
+
+        tilejson.tiles.clear();
+        assert_eq!(tilejson.tile_url_pyramid(None).count(), 0);
+    }
+
+    #[test]
+    fn test_region_fingerprint() {
+        let tilejson = osm_example();
+        let bbox = [0.0, 0.0, 90.0, 45.0];
+        let fingerprint = tilejson.region_fingerprint(bbox, 5);
+        assert_eq!(fingerprint, tilejson.clone().region_fingerprint(bbox, 5));
+        assert_ne!(fingerprint, tilejson.region_fingerprint(bbox, 6));
+
+        let updated = TileJson { version: "1.1.0".to_owned(), ..tilejson.clone() };
+        assert_eq!(updated.tiles_for_bbox(bbox, 5), tilejson.tiles_for_bbox(bbox, 5));
+        assert_ne!(updated.region_fingerprint(bbox, 5), fingerprint);
+    }
+
+    #[test]
+    fn test_tiles_within_radius() {
+        let mut tilejson = TileJson::default();
+        let mut tiles = tilejson.tiles_within_radius((0.0, 0.0), 1.0, 10);
+        tiles.sort();
+        assert_eq!(tiles, vec![(10, 511, 511), (10, 511, 512), (10, 512, 511), (10, 512, 512)]);
+
+        let [west, south, east, north] = mercator::tile_bbox(10, 512, 511);
+        let center = ((west + east) / 2.0, (south + north) / 2.0);
+        let mut tiles = tilejson.tiles_within_radius(center, 25.0, 10);
+        tiles.sort();
+        assert_eq!(tiles, vec![(10, 511, 511), (10, 512, 510), (10, 512, 511), (10, 512, 512), (10, 513, 511)]);
+
+        tilejson.bounds = Some(Bounds::from([ 0.0, 0.0, 180.0, 85.0 ]));
+        assert_eq!(tilejson.tiles_within_radius((0.0, 0.0), 1.0, 10), vec![(10, 512, 511)]);
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(tilejson.tiles_within_radius((0.0, 0.0), 1.0, 10), vec![(10, 512, 512)]);
+
+        tilejson.bounds = Some(Bounds::from([ 170.0, -10.0, -170.0, 10.0 ]));
+        tilejson.scheme = Scheme::XYZ;
+        let mut tiles = tilejson.tiles_within_radius((180.0, 0.0), 1.0, 1);
+        tiles.sort();
+        assert_eq!(tiles, vec![(1, 0, 0), (1, 0, 1), (1, 1, 0), (1, 1, 1)]);
+    }
+
+    #[test]
+    fn test_clone_is_independent() {
+        let tilejson = osm_example();
+        let mut clone = tilejson.clone();
+        clone.name = Some("Renamed".to_owned());
+        assert_eq!(tilejson.name, Some("OpenStreetMap".to_owned()));
+        assert_ne!(clone, tilejson);
+    }
+
+    #[test]
+    fn test_populated_fields() {
+        assert_eq!(TileJson::default().populated_fields(), Vec::<&str>::new());
+
+        let mut tilejson = osm_example();
+        tilejson.center = Some(Center { longitude: 0.0, latitude: 0.0, zoom: Some(2) });
+        assert_eq!(tilejson.populated_fields(), vec!["name", "description", "attribution", "center"]);
+    }
+
+    #[test]
+    fn test_strip_interactivity_and_data() {
+        let mut tilejson = osm_example();
+        tilejson.grids = vec!["https://grids.example.com/{z}/{x}/{y}.grid.json".to_owned()];
+        tilejson.template = Some("{{NAME}}".to_owned());
+        tilejson.legend = Some("<b>Legend</b>".to_owned());
+        tilejson.data = vec!["https://data.example.com/overlay.geojson".to_owned()];
+
+        tilejson.strip_interactivity();
+        assert!(tilejson.grids.is_empty());
+        assert_eq!(tilejson.template, None);
+        assert_eq!(tilejson.legend, None);
+        assert_eq!(tilejson.data.len(), 1);
+
+        tilejson.strip_data();
+        assert_eq!(tilejson, osm_example());
+    }
+
+    #[test]
+    fn test_template_variables() {
+        let mut tilejson = TileJson::default();
+        assert_eq!(tilejson.template_variables(), Vec::<String>::new());
+
+        tilejson.template = Some(
+            "{{#__teaser__}}{{NAME}}{{/__teaser__}}\
+             {{#__full__}}{{#ADMIN}}<b>{{ADMIN}}</b>{{/ADMIN}}{{^POP_EST}}n/a{{/POP_EST}}{{{NAME}}}{{/__full__}}"
+                .to_owned(),
+        );
+        assert_eq!(tilejson.template_variables(), vec!["NAME", "ADMIN", "POP_EST"]);
+    }
+
+    #[test]
+    fn test_validate() {
+        let mut tilejson = osm_example();
+        tilejson.center = Some(Center { longitude: -122.4, latitude: 37.8, zoom: Some(12) });
+        assert_eq!(tilejson.validate(), Ok(()));
+
+        tilejson.center = Some(Center { longitude: -122.4, latitude: 87.0, zoom: Some(12) });
+        assert_eq!(
+            tilejson.validate(),
+            Err(ValidationError::CenterOutOfBounds { longitude: -122.4, latitude: 87.0 })
+        );
+
+        tilejson.bounds = Some(Bounds::from([ 10.0, 45.0, 10.0, 45.0 ]));
+        assert!(matches!(tilejson.validate(), Err(ValidationError::InvalidBounds { .. })));
+    }
+
+    #[test]
+    fn test_validate_center() {
+        let mut tilejson = osm_example();
+        tilejson.center = Some(Center { longitude: 2.35, latitude: 48.85, zoom: Some(12) });
+        assert_eq!(tilejson.validate_center(), Ok(()));
+        assert_eq!(TileJson::default().validate_center(), Ok(()));
+
+        tilejson.center = Some(Center { longitude: 200.0, latitude: 48.85, zoom: Some(12) });
+        assert_eq!(
+            tilejson.validate_center(),
+            Err(ValidationError::CenterOutOfBounds { longitude: 200.0, latitude: 48.85 })
+        );
+        assert_eq!(tilejson.validate(), tilejson.validate_center());
+
+        tilejson.center = Some(Center { longitude: 2.35, latitude: 48.85, zoom: Some(19) });
+        assert_eq!(
+            tilejson.validate_center(),
+            Err(ValidationError::CenterZoomOutOfRange { zoom: 19, minzoom: 0, maxzoom: 18 })
+        );
+    }
+
+    #[test]
+    fn test_validate_no_tiles() {
+        assert_eq!(TileJson::default().validate(), Err(ValidationError::NoTiles));
+        assert_eq!(TileJson { tiles: vec![], ..osm_example() }.validate(), Err(ValidationError::NoTiles));
+        assert_eq!(ValidationError::NoTiles.to_string(), "tiles has no endpoint");
+    }
+
+    #[test]
+    fn test_validate_versions() {
+        let tilejson = TileJson { tilejson: "2.2.0".to_owned(), ..osm_example() };
+        assert_eq!(tilejson.validate(), Ok(()));
+
+        let tilejson = TileJson { tilejson: "two".to_owned(), ..osm_example() };
+        let err = tilejson.validate().unwrap_err();
+        assert_eq!(err, ValidationError::InvalidVersion { field: "tilejson", value: "two".to_owned() });
+        assert_eq!(err.to_string(), "tilejson two is not a semver version");
+
+        let tilejson = TileJson { version: "1.0".to_owned(), ..osm_example() };
+        assert_eq!(
+            tilejson.validate(),
+            Err(ValidationError::InvalidVersion { field: "version", value: "1.0".to_owned() })
+        );
+    }
+
+    #[test]
+    fn test_new() {
+        let tiles = vec!["https://tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned()];
+        let tilejson = TileJson::new(tiles.clone());
+        assert_eq!(tilejson, TileJson { tiles: tiles.clone(), ..TileJson::default() });
+        assert_eq!(tilejson, TileJson::builder().tiles(tiles).build());
+        assert_eq!(tilejson.validate(), Ok(()));
+    }
+
+    #[test]
+    // The counter in `EndpointCursor` takes no part in `Hash` or `Eq`.
+    #[allow(clippy::mutable_key_type)]
+    fn test_hash_set() {
+        let mut vector_layers = Vec::new();
+        for fields in [[("class", "String"), ("name", "String")], [("name", "String"), ("class", "String")]] {
+            let layer = VectorLayer {
+                id: "roads".to_owned(),
+                fields: fields.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect(),
+                description: None,
+                minzoom: None,
+                maxzoom: None,
+            };
+            vector_layers.push(TileJson { vector_layers: vec![layer], ..osm_example() });
+        }
+
+        let mut tilesets = HashSet::new();
+        tilesets.extend(vector_layers.iter().cloned());
+        assert_eq!(tilesets.len(), 1);
+
+        let bounds = Bounds::from([0.0, 0.0, 10.0, 10.0]);
+        tilesets.insert(TileJson { bounds: Some(Bounds { left: -0.0, ..bounds }), ..osm_example() });
+        tilesets.insert(TileJson { bounds: Some(bounds), ..osm_example() });
+        assert_eq!(tilesets.len(), 2);
+        tilesets.insert(TileJson { maxzoom: 14, ..osm_example() });
+        assert_eq!(tilesets.len(), 3);
+    }
+
+    #[test]
+    fn test_default_constants() {
+        let tilejson = TileJson::default();
+        assert_eq!(tilejson.tilejson, TileJson::DEFAULT_TILEJSON);
+        assert_eq!(tilejson.version, TileJson::DEFAULT_VERSION);
+        assert_eq!(tilejson.minzoom, TileJson::DEFAULT_MINZOOM);
+        assert_eq!(tilejson.maxzoom, TileJson::DEFAULT_MAXZOOM);
+        assert_eq!(tilejson.bounds_or_default(), TileJson::DEFAULT_BOUNDS);
+        assert_eq!(TileJson::DEFAULT_BOUNDS, Bounds::default());
+    }
+
+    #[test]
+    fn test_push_attribution() {
+        let mut tilejson = TileJson::default();
+        assert_eq!(tilejson.attributions(), Vec::<&str>::new());
+        tilejson.push_attribution("© OpenStreetMap contributors");
+        assert_eq!(tilejson.attribution, Some("© OpenStreetMap contributors".to_owned()));
+        assert_eq!(tilejson.attributions(), vec!["© OpenStreetMap contributors"]);
+
+        let mut tilejson = TileJson { attribution: Some("© Mapbox".to_owned()), ..TileJson::default() };
+        tilejson.push_attribution(String::from("© Maxar"));
+        tilejson.push_attribution("© OpenStreetMap");
+        assert_eq!(tilejson.attribution, Some("© Mapbox | © Maxar | © OpenStreetMap".to_owned()));
+        assert_eq!(tilejson.attributions(), vec!["© Mapbox", "© Maxar", "© OpenStreetMap"]);
+
+        let mut tilejson = TileJson { attribution: Some(String::new()), ..TileJson::default() };
+        tilejson.push_attribution("© Maxar");
+        assert_eq!(tilejson.attribution, Some("© Maxar".to_owned()));
+    }
+
+    #[test]
+    fn test_clamp_center_zoom() {
+        let center = Center { longitude: 0.0, latitude: 0.0, zoom: Some(20) };
+        let mut tilejson = TileJson { maxzoom: 14, center: Some(center), ..osm_example() };
+        tilejson.clamp_center_zoom();
+        assert_eq!(tilejson.center, Some(Center { zoom: Some(14), ..center }));
+        assert_eq!(tilejson.validate_center(), Ok(()));
+
+        let mut tilejson = TileJson { minzoom: 4, center: Some(Center { zoom: Some(2), ..center }), ..tilejson };
+        tilejson.clamp_center_zoom();
+        assert_eq!(tilejson.center.and_then(|center| center.zoom), Some(4));
+
+        let mut tilejson = TileJson { center: Some(Center { zoom: Some(10), ..center }), ..tilejson };
+        tilejson.clamp_center_zoom();
+        assert_eq!(tilejson.center, Some(Center { zoom: Some(10), ..center }));
+
+        let mut tilejson = TileJson { minzoom: 16, ..tilejson };
+        tilejson.clamp_center_zoom();
+        assert_eq!(tilejson.center.and_then(|center| center.zoom), Some(10));
+
+        let mut tilejson = TileJson { center: None, ..tilejson };
+        tilejson.clamp_center_zoom();
+        assert_eq!(tilejson.center, None);
+    }
+
+    #[test]
+    fn test_compute_center() {
+        let mut tilejson = TileJson {
+            bounds: Some(Bounds::from([5.9, 45.8, 10.5, 47.8])),
+            minzoom: 3,
+            maxzoom: 14,
+            ..TileJson::default()
+        };
+        let center = tilejson.compute_center();
+        assert!((center.longitude - 8.2).abs() < 1e-9 && (center.latitude - 46.8).abs() < 1e-9);
+        assert_eq!(center.zoom, Some(8));
+
+        let antimeridian = TileJson { bounds: Some(Bounds::from([170.0, -10.0, -150.0, 10.0])), ..TileJson::default() };
+        assert_eq!(antimeridian.compute_center(), Center { longitude: -170.0, latitude: 0.0, zoom: Some(15) });
+
+        tilejson.ensure_center();
+        assert_eq!(tilejson.center, Some(center));
+        let explicit = Center { longitude: 7.0, latitude: 46.0, zoom: Some(12) };
+        tilejson.center = Some(explicit);
+        tilejson.ensure_center();
+        assert_eq!(tilejson.center, Some(explicit));
+    }
+
+    #[test]
+    fn test_approx_eq() {
+        let tilejson = TileJson { bounds: Some(Bounds::from([5.9, 45.8, 10.5, 47.8])), ..osm_example() };
+        let other = TileJson { bounds: Some(Bounds { top: 47.800001, ..tilejson.bounds_or_default() }), ..tilejson.clone() };
+        assert_ne!(tilejson, other);
+        assert!(tilejson.approx_eq(&other, 1e-4));
+        assert!(!tilejson.approx_eq(&other, 0.0));
+        assert!(tilejson.approx_eq(&tilejson, 0.0));
+
+        let center = Center { longitude: 8.2, latitude: 46.8, zoom: Some(8) };
+        let tilejson = TileJson { center: Some(center), ..tilejson };
+        let nudged = TileJson { center: Some(Center { latitude: 46.800001, ..center }), ..tilejson.clone() };
+        assert!(tilejson.approx_eq(&nudged, 1e-4));
+        let rezoomed = TileJson { center: Some(Center { zoom: Some(9), ..center }), ..tilejson.clone() };
+        assert!(!tilejson.approx_eq(&rezoomed, 1e-4));
+        assert!(!tilejson.approx_eq(&TileJson { center: None, ..tilejson.clone() }, 1e-4));
+
+        let renamed = TileJson { name: Some("Other".to_owned()), ..nudged };
+        assert!(!tilejson.approx_eq(&renamed, 1e-4));
+    }
+
+    #[test]
+    fn test_bounds_area_and_overlaps() {
+        let with_bounds = |bounds: [f64; 4]| TileJson { bounds: Some(Bounds::from(bounds)), ..TileJson::default() };
+        let europe = with_bounds([-10.0, 35.0, 30.0, 60.0]);
+        let switzerland = with_bounds([5.9, 45.8, 10.5, 47.8]);
+        let japan = with_bounds([129.0, 31.0, 146.0, 46.0]);
+        let fiji = with_bounds([177.0, -21.0, -178.0, -12.0]);
+        let samoa = with_bounds([-178.5, -15.0, -168.0, -13.0]);
+
+        assert_eq!(europe.bounds_area_deg2(), 1000.0);
+        assert_eq!(TileJson::default().bounds_area_deg2(), 360.0 * 180.0);
+        assert_eq!(fiji.bounds_area_deg2(), 5.0 * 9.0);
+        assert_eq!(with_bounds([10.0, 0.0, 10.0, 5.0]).bounds_area_deg2(), 0.0);
+
+        assert!(europe.overlaps(&switzerland) && switzerland.overlaps(&europe));
+        assert!(!europe.overlaps(&japan));
+        assert!(fiji.overlaps(&samoa) && samoa.overlaps(&fiji));
+        assert!(!fiji.overlaps(&japan));
+        assert!(!europe.overlaps(&with_bounds([30.0, 35.0, 40.0, 60.0])));
+    }
+
+    #[test]
+    fn test_accessors() {
+        let tilejson = TileJson::default();
+        assert_eq!(tilejson.name(), None);
+        assert_eq!(tilejson.version(), "1.0.0");
+        assert_eq!(tilejson.scheme(), Scheme::XYZ);
+        assert_eq!(tilejson.bounds(), Bounds::default());
+
+        let tilejson = osm_example();
+        assert_eq!(tilejson.name(), Some("OpenStreetMap"));
+        assert_eq!(Some(tilejson.bounds()), tilejson.bounds);
+    }
+
+    #[test]
+    fn test_spec_version() {
+        assert_eq!(osm_example().spec_version(), SpecVersion::Other("1.0.0".to_owned()));
+        assert_eq!(TileJsonBuilder::new().build().spec_version(), SpecVersion::V2_2);
+
+        let spec_version = |tilejson: &str| TileJson { tilejson: tilejson.to_owned(), ..osm_example() }.spec_version();
+        assert_eq!(spec_version("3.0.0"), SpecVersion::V3_0);
+        assert_eq!(spec_version("2.2.1"), SpecVersion::V2_2);
+        assert_eq!(spec_version("1.0.0"), SpecVersion::Other("1.0.0".to_owned()));
+        assert_eq!(spec_version("2.2"), SpecVersion::Other("2.2".to_owned()));
+    }
+
+    #[test]
+    fn test_is_semver() {
+        for version in ["0.0.0", "2.2.0", "10.20.30", "1.0.0-alpha.1", "1.0.0-0.3.7", "1.0.0+build.5", "1.0.0-rc-1+001"] {
+            assert!(is_semver(version), "{}", version);
+        }
+        for version in ["", "1", "1.0", "1.0.0.0", "01.0.0", "1.0.0-", "1.0.0-01", "1.0.0+", "1.0.0-a..b", "v1.0.0", "1.x.0"] {
+            assert!(!is_semver(version), "{}", version);
+        }
+    }
+
+    #[test]
+    fn test_validate_zoom_range() {
+        let tilejson = TileJson { minzoom: 20, maxzoom: 5, ..osm_example() };
+        assert_eq!(tilejson.validate(), Err(ValidationError::InvalidZoomRange { minzoom: 20, maxzoom: 5 }));
+
+        let tilejson = TileJson { maxzoom: 40, ..osm_example() };
+        let err = tilejson.validate().unwrap_err();
+        assert_eq!(err, ValidationError::ZoomOutOfRange { field: "maxzoom", zoom: 40, max_zoom: 30 });
+        assert_eq!(err.to_string(), "maxzoom 40 is outside of the range 0 to 30");
+
+        let tilejson = TileJson { minzoom: 30, maxzoom: 30, ..osm_example() };
+        assert_eq!(tilejson.validate(), Ok(()));
+    }
+
+    #[test]
+    fn test_scheme_from_str_and_display() {
+        assert_eq!("XYZ".parse::<Scheme>(), Ok(Scheme::XYZ));
+        assert_eq!("tms".parse::<Scheme>(), Ok(Scheme::TMS));
+        let err = "wmts".parse::<Scheme>().unwrap_err();
+        assert_eq!(err, ParseSchemeError { scheme: "wmts".to_owned() });
+        assert_eq!(err.to_string(), "unknown tile scheme wmts, expected xyz or tms");
+
+        assert_eq!(Scheme::XYZ.to_string(), "xyz");
+        assert_eq!(Scheme::TMS.to_string(), "tms");
+    }
+
+    #[test]
+    fn test_tile_url() {
+        let mut tilejson = osm_example();
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://a.tile.openstreetmap.org/2/1/0.png".to_owned()));
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://a.tile.openstreetmap.org/2/1/3.png".to_owned()));
+        assert_eq!(tilejson.tile_url(2, 1, 3), Some("https://a.tile.openstreetmap.org/2/1/0.png".to_owned()));
+        assert_eq!(tilejson.tile_url(2, 0, 5), None);
+        assert_eq!(tilejson.tile_url(2, 4, 0), None);
+        assert_eq!(tilejson.tile_url(31, 0, 0), None);
+        assert_eq!(tilejson.tile_url_rr(2, 0, 4), None);
+    }
+
+    #[test]
+    fn test_to_scheme() {
+        let mut tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{y}.png".to_owned()],
+            ..TileJson::default()
+        };
+        tilejson.to_scheme(Scheme::TMS);
+        assert_eq!(tilejson.scheme, Scheme::TMS);
+        assert_eq!(tilejson.tiles, vec!["https://tiles.example.com/{z}/{x}/{y}.png".to_owned()]);
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://tiles.example.com/2/1/3.png".to_owned()));
+        tilejson.to_scheme(Scheme::XYZ);
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://tiles.example.com/2/1/0.png".to_owned()));
+
+        let mut tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{-y}.png".to_owned()],
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://tiles.example.com/2/1/3.png".to_owned()));
+        tilejson.to_scheme(Scheme::TMS);
+        assert_eq!(tilejson.tile_url(2, 1, 0), Some("https://tiles.example.com/2/1/3.png".to_owned()));
+    }
+
+    #[test]
+    fn test_tile_url_flipped_y() {
+        let tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{-y}.png".to_owned()],
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson.tile_url(3, 2, 1), Some("https://tiles.example.com/3/2/6.png".to_owned()));
+        assert_eq!(tilejson.tile_url(3, 2, 7), Some("https://tiles.example.com/3/2/0.png".to_owned()));
+
+        let tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{y}.png?xyz={-y}".to_owned()],
+            scheme: Scheme::TMS,
+            ..TileJson::default()
+        };
+        assert_eq!(tilejson.tile_url(3, 2, 1), Some("https://tiles.example.com/3/2/6.png?xyz=6".to_owned()));
+        let tilejson = TileJson { scheme: Scheme::XYZ, ..tilejson };
+        assert_eq!(tilejson.tile_url(3, 2, 1), Some("https://tiles.example.com/3/2/1.png?xyz=6".to_owned()));
+
+        assert!(from_xyz_url("https://tiles.example.com/{z}/{x}/{-y}.png").is_ok());
+    }
+
+    #[test]
+    fn test_tile_url_quadkey() {
+        let tilejson = TileJson {
+            tiles: vec!["https://ecn.t0.tiles.virtualearth.net/tiles/r{quadkey}.png?g=1".to_owned()],
+            scheme: Scheme::TMS,
+            ..TileJson::default()
+        };
+        assert_eq!(
+            tilejson.tile_url(3, 3, 5),
+            Some("https://ecn.t0.tiles.virtualearth.net/tiles/r213.png?g=1".to_owned())
+        );
+    }
+
+    #[test]
+    fn test_tile_urls_for_zoom() {
+        let mut tilejson = TileJson { bounds: Some(Bounds::from([5.9, 45.8, 10.5, 47.8])), ..osm_example() };
+        tilejson.tiles.truncate(1);
+        let urls: Vec<String> = tilejson.tile_urls_for_zoom(7).collect();
+        assert_eq!(urls.len(), tilejson.tiles_at_zoom(7).count());
+        assert_eq!(urls.len(), 4);
+        assert_eq!(urls[0], "https://a.tile.openstreetmap.org/7/66/44.png");
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(
+            tilejson.tile_urls_for_zoom(7).next(),
+            Some("https://a.tile.openstreetmap.org/7/66/83.png".to_owned())
+        );
+        assert_eq!(TileJson::default().tile_urls_for_zoom(0).count(), 0);
+    }
+
+    #[test]
+    fn test_normalize_placeholders() {
+        let mut tilejson = TileJson {
+            tiles: vec!["https://services.example.com/MapServer/tile/{level}/{row}/{col}.png?key={KEY}".to_owned()],
+            grids: vec!["https://services.example.com/grid/{Level}/{col}/{row}.json".to_owned()],
+            data: vec!["https://services.example.com/data/{z}/{x}/{y}.geojson".to_owned()],
+            ..TileJson::default()
+        };
+        tilejson.normalize_placeholders();
+        assert_eq!(tilejson.tiles, vec!["https://services.example.com/MapServer/tile/{z}/{y}/{x}.png?key={KEY}"]);
+        assert_eq!(tilejson.grids, vec!["https://services.example.com/grid/{z}/{x}/{y}.json"]);
+        assert_eq!(tilejson.data, vec!["https://services.example.com/data/{z}/{x}/{y}.geojson"]);
+        assert_eq!(tilejson.tile_url(3, 2, 1), Some("https://services.example.com/MapServer/tile/3/1/2.png?key={KEY}".to_owned()));
+    }
+
+    #[test]
+    fn test_tile_url_rr() {
+        let tilejson = TileJson { scheme: Scheme::TMS, ..osm_example() };
+        let urls: Vec<_> = (0..4).map(|_| tilejson.tile_url_rr(2, 1, 0).unwrap()).collect();
+        assert_eq!(
+            urls,
+            vec![
+                "https://a.tile.openstreetmap.org/2/1/3.png",
+                "https://b.tile.openstreetmap.org/2/1/3.png",
+                "https://c.tile.openstreetmap.org/2/1/3.png",
+                "https://a.tile.openstreetmap.org/2/1/3.png",
+            ]
+        );
+        assert_eq!(tilejson, TileJson { scheme: Scheme::TMS, ..osm_example() });
+        assert_eq!(TileJson::default().tile_url_rr(0, 0, 0), None);
+        assert!(!format!("{:?}", tilejson).contains("cursor"));
+    }
+
+    #[test]
+    fn test_tile_url_mixed_case_placeholders() {
+        let mut tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{Z}/{x}/{Y}.png?key={KEY}".to_owned()],
+            ..TileJson::default()
+        };
+        assert_eq!(
+            tilejson.tile_url(2, 1, 3),
+            Some("https://tiles.example.com/2/1/3.png?key={KEY}".to_owned())
+        );
+        assert_eq!(
+            tilejson.validate_warnings(),
+            vec![ValidationWarning::NonCanonicalPlaceholder { url: tilejson.tiles[0].clone() }]
+        );
+
+        tilejson.canonicalize();
+        assert_eq!(tilejson.tiles, vec!["https://tiles.example.com/{z}/{x}/{y}.png?key={KEY}".to_owned()]);
+        assert_eq!(tilejson.validate_warnings(), vec![]);
+        assert_eq!(TileJson::default().tile_url(0, 0, 0), None);
+    }
+
+    #[test]
+    fn test_canonicalize_sorts_and_dedups() {
+        let mut tilejson = TileJson {
+            tiles: vec![
+                "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+                "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+                "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+                "https://b.tile.openstreetmap.org/{Z}/{x}/{y}.png".to_owned(),
+                "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png".to_owned(),
+            ],
+            data: vec!["b.geojson".to_owned(), "a.geojson".to_owned(), "b.geojson".to_owned()],
+            ..osm_example()
+        };
+        tilejson.canonicalize();
+        assert_eq!(tilejson, TileJson { data: vec!["a.geojson".to_owned(), "b.geojson".to_owned()], ..osm_example() });
+    }
+
+    #[test]
+    fn test_contains() {
+        let mut tilejson = TileJson { bounds: Some(Bounds::from([5.9, 45.8, 10.5, 47.8])), ..TileJson::default() };
+        assert!(tilejson.contains(8.5, 47.4));
+        assert!(tilejson.contains(5.9, 47.8));
+        assert!(!tilejson.contains(4.0, 47.4));
+        assert!(!tilejson.contains(8.5, 48.0));
+
+        tilejson.bounds = Some(Bounds::from([170.0, -10.0, -170.0, 10.0]));
+        assert!(tilejson.contains(175.0, 0.0));
+        assert!(tilejson.contains(-175.0, 0.0));
+        assert!(tilejson.contains(180.0, 10.0));
+        assert!(!tilejson.contains(0.0, 0.0));
+        assert!(!tilejson.contains(175.0, 20.0));
+    }
+
+    #[test]
+    fn test_validate_warnings_non_zero_minzoom_global() {
+        let mut tilejson = TileJson { minzoom: 3, ..osm_example() };
+        assert!(tilejson.is_global());
+        assert_eq!(
+            tilejson.validate_warnings(),
+            vec![ValidationWarning::NonZeroMinzoomGlobal { minzoom: 3 }]
+        );
+
+        tilejson.bounds = Some(Bounds::from([5.9, 45.8, 10.5, 47.8]));
+        assert!(!tilejson.is_global());
+        assert_eq!(tilejson.validate_warnings(), vec![]);
+        assert_eq!(osm_example().validate_warnings(), vec![]);
+    }
+
+    #[test]
+    fn test_validate_attribution() {
+        let mut tilejson = osm_example();
+        assert_eq!(tilejson.validate_attribution(100), Ok(()));
+        assert_eq!(
+            tilejson.validate_attribution(10),
+            Err(ValidationWarning::AttributionTooLong { len: 40, max_len: 10 })
+        );
+
+        tilejson.attribution = Some("(c) OSM\ncontributors".to_owned());
+        assert_eq!(
+            tilejson.validate_attribution(100),
+            Err(ValidationWarning::AttributionControlCharacters)
+        );
+
+        assert_eq!(TileJson::default().validate_attribution(0), Ok(()));
+    }
+
+    #[test]
+    fn test_assert_supported_version() {
+        let supported = ["2.2.0", "3.0.0"];
+        assert_eq!(TileJson::default().assert_supported_version(&supported), Ok(()));
+
+        let err = osm_example().assert_supported_version(&supported).unwrap_err();
+        assert_eq!(
+            err,
+            ValidationError::UnsupportedVersion {
+                version: "1.0.0".to_owned(),
+                supported: vec!["2.2.0".to_owned(), "3.0.0".to_owned()],
+            }
+        );
+        assert_eq!(err.to_string(), "unsupported TileJSON version 1.0.0, expected one of: 2.2.0, 3.0.0");
+    }
+
+    #[test]
+    fn test_normalize_zoom_range() {
+        let mut tilejson = TileJson { minzoom: 18, maxzoom: 0, ..osm_example() };
+        assert_eq!(
+            tilejson.validate(),
+            Err(ValidationError::InvalidZoomRange { minzoom: 18, maxzoom: 0 })
+        );
+
+        tilejson.normalize_zoom_range();
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 18));
+        assert_eq!(tilejson.validate(), Ok(()));
+
+        tilejson.normalize_zoom_range();
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 18));
+    }
+
+    #[test]
+    fn test_merge_validated() {
+        let mut tilejson = osm_example();
+        tilejson.center = Some(Center { longitude: -122.4, latitude: 37.8, zoom: Some(12) });
+        let europe = TileJson { bounds: Some(Bounds::from([ -10.0, 35.0, 30.0, 60.0 ])), ..TileJson::default() };
+
+        let mut merged = tilejson.clone();
+        merged.merge(&europe);
+        assert!(merged.validate().is_err());
+
+        assert_eq!(
+            tilejson.merge_validated(&europe, &MergePolicy::default()),
+            Err(ValidationError::CenterOutOfBounds { longitude: -122.4, latitude: 37.8 })
+        );
+        assert_eq!(tilejson.bounds, osm_example().bounds);
+
+        let overlay = TileJson { maxzoom: 14, ..TileJson::default() };
+        assert_eq!(tilejson.merge_validated(&overlay, &MergePolicy::default()), Ok(()));
+        assert_eq!(tilejson.maxzoom, 14);
+    }
+
+    #[test]
+    fn test_tiles_at_zoom() {
+        let mut tilejson = TileJson { bounds: Some(Bounds::from([ 0.0, 0.0, 90.0, 45.0 ])), ..TileJson::default() };
+        assert_eq!(tilejson.tiles_at_zoom(3).collect::<Vec<_>>(), vec![(3, 4, 2), (3, 4, 3), (3, 5, 2), (3, 5, 3)]);
+        assert_eq!(tilejson.tiles_at_zoom(0).collect::<Vec<_>>(), vec![(0, 0, 0)]);
+        assert_eq!(tilejson.tiles_at_zoom(12).count(), 1024 * 575);
+
+        tilejson.scheme = Scheme::TMS;
+        assert_eq!(tilejson.tiles_at_zoom(2).collect::<Vec<_>>(), vec![(2, 2, 2)]);
+
+        tilejson.bounds = Some(Bounds::from([ 170.0, -10.0, -170.0, 10.0 ]));
+        assert_eq!(tilejson.tiles_at_zoom(2).count(), 4);
+
+        tilejson.bounds = Some(Bounds::from([ 0.0, 0.0, 0.0, 45.0 ]));
+        assert_eq!(tilejson.tiles_at_zoom(2).count(), 0);
+    }
+
+    #[test]
+    fn test_tiles_added_by_zoom_change() {
+        let tilejson = TileJson {
+            maxzoom: 3,
+            bounds: Some(Bounds::from([ 0.0, 0.0, 90.0, 45.0 ])),
+            ..TileJson::default()
+        };
+        let added: Vec<_> = tilejson.tiles_added_by_zoom_change(1).collect();
+        assert_eq!(added, vec![(2, 2, 1), (3, 4, 2), (3, 4, 3), (3, 5, 2), (3, 5, 3)]);
+
+        assert_eq!(tilejson.tiles_added_by_zoom_change(3).count(), 0);
+        assert_eq!(tilejson.tiles_added_by_zoom_change(10).count(), 0);
+    }
+
+    #[test]
+    fn test_validate_hosts() {
+        let mut tilejson = osm_example();
+        let allowed = ["a.tile.openstreetmap.org", "b.tile.openstreetmap.org", "C.TILE.OPENSTREETMAP.ORG"];
+        assert_eq!(tilejson.validate_hosts(&allowed), Ok(()));
+
+        tilejson.grids = vec![
+            "/grids/{z}/{x}/{y}.json".to_owned(),
+            "https://{host}/grids/{z}/{x}/{y}.json".to_owned(),
         ];
-        tilejson.maxzoom = 18;
-        tilejson.bounds = vec![ -180.0, -85.0, 180.0, 85.0 ];
+        assert_eq!(tilejson.validate_hosts(&allowed), Ok(()));
 
-        assert_eq!(decode(&encoded_str), tilejson);
+        tilejson.data = vec!["https://user@cdn.example.com:8080/data.geojson".to_owned()];
+        assert_eq!(
+            tilejson.validate_hosts(&allowed),
+            Err(ValidationError::DisallowedHost {
+                url: "https://user@cdn.example.com:8080/data.geojson".to_owned(),
+                host: "cdn.example.com".to_owned(),
+            })
+        );
     }
 }
@@ -0,0 +1,73 @@
+//! The binary encodings of `TileJson`, CBOR behind the `cbor` feature and
+//! MessagePack behind the `msgpack` feature. Both carry the same fields as
+//! the JSON encoding.
+
+use super::TileJson;
+use crate::error::Error;
+
+impl TileJson {
+    /// Encodes the document as CBOR.
+    #[cfg(feature = "cbor")]
+    pub fn to_cbor(&self) -> Result<Vec<u8>, Error> {
+        let mut cbor = Vec::new();
+        ciborium::ser::into_writer(self, &mut cbor)?;
+        Ok(cbor)
+    }
+
+    /// Decodes a document from CBOR, failing if it isn't valid CBOR or lacks
+    /// required fields such as `tilejson` and `tiles`.
+    #[cfg(feature = "cbor")]
+    pub fn from_cbor(cbor: &[u8]) -> Result<TileJson, Error> {
+        Ok(ciborium::de::from_reader(cbor)?)
+    }
+
+    /// Encodes the document as MessagePack, with fields keyed by name.
+    #[cfg(feature = "msgpack")]
+    pub fn to_msgpack(&self) -> Result<Vec<u8>, Error> {
+        Ok(rmp_serde::to_vec_named(self)?)
+    }
+
+    /// Decodes a document from MessagePack, failing if it isn't valid
+    /// MessagePack or lacks required fields such as `tilejson` and `tiles`.
+    #[cfg(feature = "msgpack")]
+    pub fn from_msgpack(msgpack: &[u8]) -> Result<TileJson, Error> {
+        Ok(rmp_serde::from_slice(msgpack)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tilejson::tests::osm_example;
+
+    use serde_json::Value;
+
+    fn osm_example_with_extra() -> TileJson {
+        let mut tilejson = osm_example();
+        tilejson.other.insert("mtime".to_owned(), Value::from(1_600_000_000));
+        tilejson
+    }
+
+    #[test]
+    #[cfg(feature = "cbor")]
+    fn test_cbor_roundtrip() {
+        let tilejson = osm_example_with_extra();
+        let cbor = tilejson.to_cbor().unwrap();
+        assert_eq!(TileJson::from_cbor(&cbor).unwrap(), tilejson);
+
+        assert!(matches!(TileJson::from_cbor(&cbor[..cbor.len() / 2]), Err(Error::CborDecode(_))));
+    }
+
+    #[test]
+    #[cfg(feature = "msgpack")]
+    fn test_msgpack_roundtrip() {
+        let tilejson = osm_example_with_extra();
+        let msgpack = tilejson.to_msgpack().unwrap();
+        assert_eq!(TileJson::from_msgpack(&msgpack).unwrap(), tilejson);
+
+        assert!(matches!(
+            TileJson::from_msgpack(&msgpack[..msgpack.len() / 2]),
+            Err(Error::MsgpackDecode(_))
+        ));
+    }
+}
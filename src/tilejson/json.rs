@@ -0,0 +1,1408 @@
+//! The JSON encoding of `TileJson` and everything built on it, behind the
+//! default `serde` feature.
+
+use serde::de::{self, Deserializer, Unexpected, Visitor};
+use serde::{Deserialize, Serialize, Serializer};
+use serde_json::{json, Map, Value};
+
+use super::{
+    canonical_placeholders, default_mapbox_logo, default_tilejson, endpoint_extension,
+    rewrite_placeholders, Scheme, TileJson,
+};
+use crate::bounds::Bounds;
+use crate::center::Center;
+use crate::error::Error;
+use crate::hash::Fnv1a;
+
+use std::convert::TryFrom;
+use std::fmt;
+use std::fs;
+use std::hash::Hasher;
+use std::io::{self, BufRead, BufReader, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+impl FromStr for TileJson {
+    type Err = Error;
+
+    /// Decodes a TileJSON document, like `decode`.
+    fn from_str(tilejson: &str) -> Result<Self, Self::Err> {
+        decode(tilejson)
+    }
+}
+
+impl TryFrom<&str> for TileJson {
+    type Error = Error;
+
+    /// Decodes a TileJSON document, like `decode`.
+    fn try_from(tilejson: &str) -> Result<Self, Self::Error> {
+        decode(tilejson)
+    }
+}
+
+impl fmt::Display for TileJson {
+    /// Writes the document as compact JSON, like `encode`, or a placeholder
+    /// naming the error in the unlikely case that it can't be encoded.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match serde_json::to_string(self) {
+            Ok(json) => f.write_str(&json),
+            Err(err) => write!(f, "<unencodable TileJSON: {}>", err),
+        }
+    }
+}
+
+impl Serialize for Scheme {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(self.as_str())
+    }
+}
+
+impl<'de> Deserialize<'de> for Scheme {
+    /// Decodes `xyz` and `tms` regardless of case, and any other string as
+    /// `Scheme::Other`.
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let scheme = String::deserialize(deserializer)?;
+        Ok(scheme.parse().unwrap_or(Scheme::Other(scheme)))
+    }
+}
+
+impl From<TileJson> for Value {
+    fn from(tilejson: TileJson) -> Self {
+        serde_json::to_value(tilejson).expect("a TileJson always encodes to JSON")
+    }
+}
+
+impl TryFrom<&Value> for TileJson {
+    type Error = Error;
+
+    /// Decodes a TileJSON document held in a JSON tree, e.g. a source embedded
+    /// in a style, without going through a string.
+    fn try_from(value: &Value) -> Result<Self, Self::Error> {
+        Ok(TileJson::deserialize(value)?)
+    }
+}
+
+impl TileJson {
+    /// Decodes a TileJSON document straight from `reader`, e.g. a file or a
+    /// socket, without reading it into a string first.
+    pub fn from_reader<R: Read>(reader: R) -> Result<TileJson, Error> {
+        decode_reader(reader)
+    }
+
+    /// Encodes the document as compact JSON straight into `writer`.
+    pub fn to_writer<W: Write>(&self, writer: W) -> Result<(), Error> {
+        serde_json::to_writer(writer, self)?;
+        Ok(())
+    }
+
+    /// Reads and decodes the TileJSON document stored at `path`.
+    pub fn from_file<P: AsRef<Path>>(path: P) -> Result<TileJson, Error> {
+        decode(&fs::read_to_string(path)?)
+    }
+
+    /// Encodes the document and writes it to `path`, replacing any existing
+    /// file.
+    pub fn to_file<P: AsRef<Path>>(&self, path: P) -> Result<(), Error> {
+        fs::write(path, encode(self)?)?;
+        Ok(())
+    }
+
+    /// Returns a JSON object holding only the top-level fields whose encoded
+    /// value differs from `base`, so `base` plus the patch can be stored
+    /// instead of the full document. Merging a document decoded from the
+    /// patch onto `base` with `merge` reproduces this document.
+    ///
+    /// Since `merge` only takes set, non-default values, a patch can't clear
+    /// an optional field or reset a field to its default: such differences
+    /// are left out.
+    pub fn to_patch(&self, base: &TileJson) -> Value {
+        let to_object = |tilejson: &TileJson| match serde_json::to_value(tilejson) {
+            Ok(Value::Object(object)) => object,
+            _ => unreachable!("a TileJson always encodes to a JSON object"),
+        };
+        let base = to_object(base);
+        let defaults = to_object(&TileJson::default());
+        let patch: Map<String, Value> = to_object(self)
+            .into_iter()
+            .filter(|(key, value)| {
+                base.get(key) != Some(value) && defaults.get(key) != Some(value)
+            })
+            .collect();
+        Value::Object(patch)
+    }
+
+    /// Lists the rows of an MBTiles `metadata` table describing this
+    /// tileset, the inverse of `from_mbtiles_metadata`: `bounds` and
+    /// `center` are joined with commas and `vector_layers` go in the `json`
+    /// row. `format` is the one `to_tileserver_config` declares, and is left
+    /// out when neither the endpoints nor `format` give one, as are rows for
+    /// unset options and empty lists. String entries of `other`, such as the
+    /// rows `from_mbtiles_metadata` doesn't know, are written back as they
+    /// are, while other entries have no place in the table and are dropped.
+    pub fn to_mbtiles_metadata(&self) -> Vec<(String, String)> {
+        let mut rows = Vec::new();
+        let mut push = |key: &str, value: String| rows.push((key.to_owned(), value));
+        if let Some(name) = &self.name {
+            push("name", name.clone());
+        }
+        if let Some(format) = self.known_format() {
+            push("format", format.to_owned());
+        }
+        if let Some(bounds) = self.bounds {
+            let Bounds { left, bottom, right, top } = bounds;
+            push("bounds", format!("{},{},{},{}", left, bottom, right, top));
+        }
+        if let Some(center) = self.center {
+            let Center { longitude, latitude, zoom } = center;
+            match zoom {
+                Some(zoom) => push("center", format!("{},{},{}", longitude, latitude, zoom)),
+                None => push("center", format!("{},{}", longitude, latitude)),
+            }
+        }
+        push("minzoom", self.minzoom.to_string());
+        push("maxzoom", self.maxzoom.to_string());
+        if let Some(attribution) = &self.attribution {
+            push("attribution", attribution.clone());
+        }
+        if let Some(description) = &self.description {
+            push("description", description.clone());
+        }
+        push("version", self.version.clone());
+        for (key, value) in &self.other {
+            if let Value::String(value) = value {
+                push(key, value.clone());
+            }
+        }
+        if !self.vector_layers.is_empty() {
+            push("json", json!({ "vector_layers": self.vector_layers }).to_string());
+        }
+        rows
+    }
+
+    /// Builds the `data` section of a TileServer GL 4.x `config.json`
+    /// declaring this tileset under `id`.
+    ///
+    /// The entry expects the tiles in `<id>.mbtiles` under the configured
+    /// `paths.mbtiles` directory, and overrides the served TileJSON with this
+    /// document's name, attribution, format, zoom range, bounds and center.
+    /// Styles can't be derived from a TileJSON and are left to the caller.
+    pub fn to_tileserver_config(&self, id: &str) -> Value {
+        let mut tilejson = json!({
+            "format": self.inferred_format(),
+            "minzoom": self.minzoom,
+            "maxzoom": self.maxzoom,
+            "bounds": self.bounds_or_default(),
+        });
+        let fields = [("name", &self.name), ("attribution", &self.attribution)];
+        for (key, value) in fields.iter() {
+            if let Some(value) = value {
+                tilejson[*key] = json!(value);
+            }
+        }
+        if let Some(center) = &self.center {
+            tilejson["center"] = json!(center);
+        }
+
+        json!({
+            "data": {
+                id: {
+                    "mbtiles": format!("{}.mbtiles", id),
+                    "tilejson": tilejson,
+                }
+            }
+        })
+    }
+
+    /// Builds the options of a CesiumJS `UrlTemplateImageryProvider` for the
+    /// first tile endpoint: `url`, `minimumLevel`, `maximumLevel`,
+    /// `rectangle` (`bounds` in radians), `tileWidth`/`tileHeight` and, when
+    /// there's an attribution, `credit`.
+    ///
+    /// Cesium numbers `{y}` from the north like XYZ, and `{reverseY}` from the
+    /// south like TMS, so for TMS tilesets the `{y}` placeholder is rewritten
+    /// to `{reverseY}` and `{-y}` to `{y}`, while for XYZ ones `{-y}` becomes
+    /// `{reverseY}`. Placeholders are matched regardless of case. The
+    /// provider's default web mercator tiling scheme matches the one TileJSON
+    /// assumes.
+    pub fn to_cesium_imagery(&self) -> Value {
+        let (y, flipped_y) = match self.scheme {
+            Scheme::XYZ | Scheme::Other(_) => ("y", "reverseY"),
+            Scheme::TMS => ("reverseY", "y"),
+        };
+        let url = self.tiles.first().map(|url| {
+            rewrite_placeholders(&canonical_placeholders(url), |name| match name {
+                "y" => Some(y),
+                "-y" => Some(flipped_y),
+                _ => None,
+            })
+        });
+        let [west, south, east, north] = self.bounds_extent().unwrap_or([-180.0, -90.0, 180.0, 90.0]);
+        let mut options = json!({
+            "url": url,
+            "minimumLevel": self.minzoom,
+            "maximumLevel": self.maxzoom,
+            "rectangle": {
+                "west": west.to_radians(),
+                "south": south.to_radians(),
+                "east": east.to_radians(),
+                "north": north.to_radians(),
+            },
+            "tileWidth": self.tile_size(),
+            "tileHeight": self.tile_size(),
+        });
+        if let Some(attribution) = &self.attribution {
+            options["credit"] = json!(attribution);
+        }
+        options
+    }
+
+    /// Builds a Mapbox GL style `source` serving this tileset: `type`
+    /// (`vector` when `is_vector`, `raster` otherwise), `tiles`, `minzoom`,
+    /// `maxzoom`, `bounds` and, when there's one, `attribution`.
+    ///
+    /// TMS tilesets also get `"scheme": "tms"`, and raster ones their
+    /// `tileSize`, since Mapbox GL assumes 512 pixel tiles where TileJSON
+    /// assumes 256.
+    pub fn to_gl_source(&self) -> Value {
+        let vector = self.is_vector();
+        let mut source = json!({
+            "type": if vector { "vector" } else { "raster" },
+            "tiles": self.tiles,
+            "minzoom": self.minzoom,
+            "maxzoom": self.maxzoom,
+            "bounds": self.bounds_or_default(),
+        });
+        if !vector {
+            source["tileSize"] = json!(self.tile_size());
+        }
+        if self.scheme == Scheme::TMS {
+            source["scheme"] = json!("tms");
+        }
+        if let Some(attribution) = &self.attribution {
+            source["attribution"] = json!(attribution);
+        }
+        source
+    }
+
+    /// Generates a stable, URL-safe identifier for the tileset.
+    ///
+    /// The id has the form `<slug>-<hash>`, where `<slug>` is `name`
+    /// lowercased with every run of non-alphanumeric characters replaced by a
+    /// single `-` (`tileset` when there's no usable name), and `<hash>` is
+    /// the first 12 hexadecimal digits of the FNV-1a hash of the canonical
+    /// document, in which the order and duplicates of endpoints don't count.
+    ///
+    /// The same document always yields the same id, whatever its `id` field
+    /// holds. Two tilesets sharing a name only collide if their 48-bit hashes
+    /// do, which is unlikely but not impossible; the hash isn't cryptographic.
+    pub fn generate_id(&self) -> String {
+        let canonical = TileJson { id: None, ..self.canonicalized() };
+        let canonical = serde_json::to_value(canonical)
+            .expect("a TileJson always converts to a JSON value");
+        let mut hasher = Fnv1a::default();
+        hasher.write(canonical.to_string().as_bytes());
+
+        let slug = self.name.as_deref().map(slugify).unwrap_or_default();
+        let slug = if slug.is_empty() { "tileset" } else { &slug };
+        format!("{}-{:012x}", slug, hasher.finish() >> 16)
+    }
+
+    /// Fills `id` from `generate_id` unless it's already set.
+    pub fn ensure_id(&mut self) {
+        if self.id.is_none() {
+            self.id = Some(self.generate_id());
+        }
+    }
+
+    /// The tile format, taken from the extension of the first tile endpoint
+    /// when it's a known one and from `format` otherwise.
+    fn inferred_format(&self) -> String {
+        self.known_format().unwrap_or(self.format()).to_owned()
+    }
+
+    /// Like `inferred_format`, but `None` rather than the `pbf` default when
+    /// neither the endpoints nor `format` say what the tiles are.
+    fn known_format(&self) -> Option<&str> {
+        self.tiles.first().and_then(|url| endpoint_extension(url)).or(self.format.as_deref())
+    }
+
+    /// A `canonicalize`d copy of the document.
+    fn canonicalized(&self) -> TileJson {
+        let mut canonical = self.clone();
+        canonical.canonicalize();
+        canonical
+    }
+}
+
+/// Lowercases `text`, replacing every run of non-alphanumeric characters with a
+/// single `-`.
+fn slugify(text: &str) -> String {
+    let lowercase = text.to_ascii_lowercase();
+    let words: Vec<&str> = lowercase
+        .split(|c: char| !c.is_ascii_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .collect();
+    words.join("-")
+}
+
+pub(super) fn is_default_mapbox_logo(mapbox_logo: &bool) -> bool {
+    *mapbox_logo == default_mapbox_logo()
+}
+
+/// Reads a zoom level given either as a number or as a numeric string such
+/// as `"18"`, which some tile servers emit.
+pub(super) fn string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<u8, D::Error> {
+    deserializer.deserialize_any(ZoomVisitor)
+}
+
+/// Like `string_or_number`, for an optional zoom level.
+pub(super) fn option_string_or_number<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Option<u8>, D::Error> {
+    #[derive(Deserialize)]
+    struct Zoom(#[serde(deserialize_with = "string_or_number")] u8);
+
+    Ok(Option::<Zoom>::deserialize(deserializer)?.map(|Zoom(zoom)| zoom))
+}
+
+/// Reads a list of endpoints given either as an array or as a bare string,
+/// which some UTFGrid producers emit for a single endpoint.
+pub(super) fn string_or_vec<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vec<String>, D::Error> {
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum Endpoints {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    Ok(match Endpoints::deserialize(deserializer)? {
+        Endpoints::One(endpoint) => vec![endpoint],
+        Endpoints::Many(endpoints) => endpoints,
+    })
+}
+
+struct ZoomVisitor;
+
+impl<'de> Visitor<'de> for ZoomVisitor {
+    type Value = u8;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str("a zoom level as an integer or a numeric string")
+    }
+
+    fn visit_u64<E: de::Error>(self, zoom: u64) -> Result<u8, E> {
+        u8::try_from(zoom).map_err(|_| E::invalid_value(Unexpected::Unsigned(zoom), &self))
+    }
+
+    fn visit_i64<E: de::Error>(self, zoom: i64) -> Result<u8, E> {
+        u8::try_from(zoom).map_err(|_| E::invalid_value(Unexpected::Signed(zoom), &self))
+    }
+
+    fn visit_str<E: de::Error>(self, zoom: &str) -> Result<u8, E> {
+        zoom.trim().parse().map_err(|_| E::invalid_value(Unexpected::Str(zoom), &self))
+    }
+}
+
+/// The zoom fields that a document may give as numeric strings.
+const ZOOM_FIELDS: [&str; 3] = ["minzoom", "maxzoom", "fillzoom"];
+
+/// The coordinate fields whose values a document may give as numeric strings.
+const COORDINATE_FIELDS: [&str; 2] = ["bounds", "center"];
+
+/// What `decode_with` requires of a document.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct DecodeOptions {
+    /// Fail if `tiles` is missing, rather than decoding it as empty.
+    pub require_tiles: bool,
+    /// Fail if `tilejson` is missing, rather than taking it to be `2.2.0`.
+    pub require_tilejson_version: bool,
+    /// Accept numeric strings such as `"14"` for the zoom levels and the
+    /// `bounds` and `center` coordinates, rather than failing on them.
+    pub coerce_string_numbers: bool,
+    /// Swap a `minzoom` greater than `maxzoom` into order, as
+    /// `TileJson::normalize_zoom_range` does, rather than keeping it for
+    /// `validate` to reject.
+    pub normalize_zoom_range: bool,
+}
+
+impl DecodeOptions {
+    /// Rejects any deviation from the spec: `tilejson` and `tiles` are
+    /// required, and numbers must be given as JSON numbers.
+    pub fn strict() -> DecodeOptions {
+        DecodeOptions {
+            require_tiles: true,
+            require_tilejson_version: true,
+            coerce_string_numbers: false,
+            normalize_zoom_range: false,
+        }
+    }
+
+    /// Accepts anything that parses: missing `tilejson` and `tiles` are
+    /// defaulted, numeric strings are accepted and a swapped zoom range is
+    /// put into order.
+    pub fn lenient() -> DecodeOptions {
+        DecodeOptions {
+            require_tiles: false,
+            require_tilejson_version: false,
+            coerce_string_numbers: true,
+            normalize_zoom_range: true,
+        }
+    }
+}
+
+impl Default for DecodeOptions {
+    /// The options used by `decode`: like `DecodeOptions::strict`, but
+    /// numeric strings are accepted since many producers emit them.
+    fn default() -> Self {
+        DecodeOptions { coerce_string_numbers: true, ..DecodeOptions::strict() }
+    }
+}
+
+/// Decodes a TileJSON document, failing if it isn't valid JSON or lacks
+/// required fields such as `tilejson` and `tiles`. Zoom levels and
+/// coordinates given as numeric strings are accepted.
+pub fn decode(tilejson: &str) -> Result<TileJson, Error> {
+    decode_with(tilejson, &DecodeOptions::default())
+}
+
+/// Decodes a TileJSON document, requiring only what `options` asks for.
+pub fn decode_with(tilejson: &str, options: &DecodeOptions) -> Result<TileJson, Error> {
+    if *options == DecodeOptions::default() {
+        return Ok(serde_json::from_str(tilejson)?);
+    }
+    let mut value: Value = serde_json::from_str(tilejson)?;
+    if let Value::Object(object) = &mut value {
+        if !options.require_tilejson_version {
+            object.entry("tilejson").or_insert_with(|| Value::from(default_tilejson()));
+        }
+        if !options.require_tiles {
+            object.entry("tiles").or_insert_with(|| Value::Array(Vec::new()));
+        }
+        if !options.coerce_string_numbers {
+            if let Some(field) = string_number_field(object) {
+                let message = format!("{} must be given as numbers, not strings", field);
+                return Err(<serde_json::Error as de::Error>::custom(message).into());
+            }
+        }
+    }
+    let mut tilejson: TileJson = serde_json::from_value(value)?;
+    if options.normalize_zoom_range {
+        tilejson.normalize_zoom_range();
+    }
+    Ok(tilejson)
+}
+
+/// The first zoom or coordinate field of `object` holding a string.
+fn string_number_field(object: &Map<String, Value>) -> Option<&'static str> {
+    let zoom = ZOOM_FIELDS.iter().find(|field| object.get(**field).is_some_and(Value::is_string));
+    let coordinates = COORDINATE_FIELDS.iter().find(|field| match object.get(**field) {
+        Some(Value::Array(values)) => values.iter().any(Value::is_string),
+        _ => false,
+    });
+    zoom.or(coordinates).copied()
+}
+
+/// Decodes a document with `DecodeOptions::lenient`, fixing up common
+/// producer mistakes instead of failing or leaving them for `validate` to
+/// reject:
+/// - a missing `tilejson` is taken to be `2.2.0`, as in legacy metadata;
+/// - a missing `tiles` is taken to be empty;
+/// - a `minzoom` greater than `maxzoom` is swapped into order.
+///
+/// Use `decode_with` and `normalize_zoom_range: false` to keep a swapped
+/// zoom range as it is.
+pub fn decode_lenient(tilejson: &str) -> Result<TileJson, Error> {
+    decode_with(tilejson, &DecodeOptions::lenient())
+}
+
+/// Builds a document from the rows of an MBTiles `metadata` table.
+///
+/// `bounds` and `center` are split at their commas, the `vector_layers` of
+/// the `json` row are taken over, numeric rows such as `tileSize` are read
+/// as numbers like the zoom levels, and any row the spec doesn't know is
+/// kept in `other`. MBTiles files don't carry endpoints, so `tiles` is empty and
+/// `tilejson` is `2.2.0`.
+pub fn from_mbtiles_metadata(pairs: &[(String, String)]) -> Result<TileJson, Error> {
+    let mut object = Map::new();
+    object.insert("tilejson".to_owned(), Value::from(default_tilejson()));
+    object.insert("tiles".to_owned(), Value::Array(Vec::new()));
+    for (key, value) in pairs {
+        match key.as_str() {
+            "bounds" | "center" => {
+                let coordinates = value.split(',').map(|coordinate| Value::from(coordinate.trim())).collect();
+                object.insert(key.clone(), Value::Array(coordinates));
+            }
+            "tileSize" => {
+                let tile_size = value.trim().parse::<u32>().map_or_else(|_| Value::from(value.as_str()), Value::from);
+                object.insert(key.clone(), tile_size);
+            }
+            "json" => {
+                let mut json: Value = serde_json::from_str(value)?;
+                if let Some(vector_layers) = json.get_mut("vector_layers") {
+                    object.insert("vector_layers".to_owned(), vector_layers.take());
+                }
+            }
+            _ => {
+                object.insert(key.clone(), Value::from(value.as_str()));
+            }
+        }
+    }
+    Ok(serde_json::from_value(Value::Object(object))?)
+}
+
+/// Decodes a TileJSON document from raw bytes, like `decode`. Bytes that
+/// aren't valid UTF-8 are reported as an error, as is any other malformed
+/// input: this never panics, which makes it a suitable fuzzing target.
+pub fn decode_bytes(tilejson: &[u8]) -> Result<TileJson, Error> {
+    Ok(serde_json::from_slice(tilejson)?)
+}
+
+/// Decodes a TileJSON document read from `reader`, like `decode`. Fails
+/// with `Error::Io` if reading fails and `Error::Json` if the input isn't a
+/// valid document.
+pub fn decode_reader<R: Read>(reader: R) -> Result<TileJson, Error> {
+    Ok(serde_json::from_reader(reader)?)
+}
+
+/// Decodes a JSON array of TileJSON documents, as served by tile catalogs,
+/// failing if any of them is invalid.
+pub fn decode_many(tilejsons: &str) -> Result<Vec<TileJson>, Error> {
+    Ok(serde_json::from_str(tilejsons)?)
+}
+
+/// Decodes the elements of a JSON array of TileJSON documents one after
+/// the other, like `decode_many` but without reading the whole array first.
+///
+/// An element that fails to decode, or input that isn't an array, yields an
+/// error after the documents before it and ends the iteration.
+pub fn decode_stream<R: Read>(reader: R) -> impl Iterator<Item = Result<TileJson, Error>> {
+    ArrayStream { reader: BufReader::new(reader), started: false, done: false }
+}
+
+/// The elements of a JSON array read from `reader`, decoded one at a time.
+struct ArrayStream<R> {
+    reader: BufReader<R>,
+    started: bool,
+    done: bool,
+}
+
+impl<R: Read> ArrayStream<R> {
+    /// Skips whitespace and returns the next byte without consuming it, or
+    /// `None` at the end of the input.
+    fn peek(&mut self) -> io::Result<Option<u8>> {
+        loop {
+            let buf = match self.reader.fill_buf() {
+                Ok(buf) => buf,
+                Err(err) if err.kind() == io::ErrorKind::Interrupted => continue,
+                Err(err) => return Err(err),
+            };
+            if buf.is_empty() {
+                return Ok(None);
+            }
+            match buf.iter().position(|byte| !byte.is_ascii_whitespace()) {
+                Some(start) => {
+                    let byte = buf[start];
+                    self.reader.consume(start);
+                    return Ok(Some(byte));
+                }
+                None => {
+                    let len = buf.len();
+                    self.reader.consume(len);
+                }
+            }
+        }
+    }
+
+    /// Moves past the `[` before the first element or the `,` before any
+    /// other, returning `false` at the closing `]`.
+    fn advance(&mut self) -> Result<bool, Error> {
+        let (separator, expected) = if self.started {
+            (b',', "`,` or `]` after a TileJSON document")
+        } else {
+            (b'[', "an array of TileJSON documents")
+        };
+        match self.peek()? {
+            Some(b']') if self.started => return Ok(false),
+            Some(byte) if byte == separator => self.reader.consume(1),
+            _ => {
+                let message = format!("expected {}", expected);
+                return Err(<serde_json::Error as de::Error>::custom(message).into());
+            }
+        }
+        if !self.started {
+            self.started = true;
+            if self.peek()? == Some(b']') {
+                return Ok(false);
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<R: Read> Iterator for ArrayStream<R> {
+    type Item = Result<TileJson, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.done {
+            return None;
+        }
+        let tilejson = match self.advance() {
+            Ok(true) => {
+                let mut deserializer = serde_json::Deserializer::from_reader(&mut self.reader);
+                TileJson::deserialize(&mut deserializer).map_err(Error::from)
+            }
+            Ok(false) => {
+                self.done = true;
+                return None;
+            }
+            Err(err) => Err(err),
+        };
+        self.done = tilejson.is_err();
+        Some(tilejson)
+    }
+}
+
+/// Decodes a TileJSON document read to the end from an asynchronous
+/// `reader`, like `decode_reader`. Fails with `Error::Io` if reading fails
+/// and `Error::Json` if the input isn't a valid document.
+#[cfg(feature = "tokio")]
+pub async fn decode_async_reader<R>(mut reader: R) -> Result<TileJson, Error>
+where
+    R: tokio::io::AsyncRead + Unpin,
+{
+    use tokio::io::AsyncReadExt;
+
+    let mut buf = Vec::new();
+    reader.read_to_end(&mut buf).await?;
+    Ok(serde_json::from_slice(&buf)?)
+}
+
+/// Encodes a document as compact JSON.
+pub fn encode(tilejson: &TileJson) -> Result<String, Error> {
+    Ok(serde_json::to_string(tilejson)?)
+}
+
+/// Encodes a document as indented JSON, for files meant to be read by people.
+pub fn encode_pretty(tilejson: &TileJson) -> Result<String, Error> {
+    Ok(serde_json::to_string_pretty(tilejson)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::error::ValidationError;
+    use crate::tilejson::tests::osm_example;
+    use crate::tilejson::{ExtrasPolicy, MergePolicy, VectorLayer};
+
+    #[test]
+    fn test_encode_default() {
+        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":[],"minzoom":0,"maxzoom":30}"#;
+        let tilejson = TileJson::default();
+        assert_eq!(encode(&tilejson).unwrap(), encoded_str);
+    }
+
+    #[test]
+    fn test_display() {
+        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":[],"minzoom":0,"maxzoom":30}"#;
+        assert_eq!(format!("{}", TileJson::default()), encoded_str);
+        assert_eq!(osm_example().to_string(), encode(&osm_example()).unwrap());
+    }
+
+    #[test]
+    fn test_decode_default() {
+        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":[],"minzoom":0,"maxzoom":30}"#;
+        let tilejson = TileJson::default();
+        assert_eq!(decode(encoded_str).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_decode_missing_bounds() {
+        let tilejson = decode(r#"{"tilejson":"2.2.0","tiles":["https://example.com/{z}/{x}/{y}.png"]}"#).unwrap();
+        assert_eq!(tilejson.bounds, None);
+        assert_eq!(tilejson.bounds_or_default(), Bounds::from([-180.0, -90.0, 180.0, 90.0]));
+        assert!(!encode(&tilejson).unwrap().contains("bounds"));
+
+        let world = decode(r#"{"tilejson":"2.2.0","tiles":[],"bounds":[-180,-90,180,90]}"#).unwrap();
+        assert_eq!(world.bounds, Some(TileJson::DEFAULT_BOUNDS));
+        assert_ne!(world, TileJson::default());
+    }
+
+    #[test]
+    fn test_encode_example() {
+        let encoded_str = r#"{"tilejson":"1.0.0","name":"OpenStreetMap","description":"A free editable map of the whole world.","version":"1.0.0","attribution":"(c) OpenStreetMap contributors, CC-BY-SA","scheme":"xyz","tiles":["https://a.tile.openstreetmap.org/{z}/{x}/{y}.png","https://b.tile.openstreetmap.org/{z}/{x}/{y}.png","https://c.tile.openstreetmap.org/{z}/{x}/{y}.png"],"minzoom":0,"maxzoom":18,"bounds":[-180.0,-85.0,180.0,85.0]}"#;
+        let tilejson = osm_example();
+
+        assert_eq!(encode(&tilejson).unwrap(), encoded_str);
+    }
+
+    #[test]
+    fn test_decode_example() {
+        let encoded_str = r#"{
+            "tilejson": "1.0.0",
+            "name": "OpenStreetMap",
+            "description": "A free editable map of the whole world.",
+            "version": "1.0.0",
+            "attribution": "(c) OpenStreetMap contributors, CC-BY-SA",
+            "scheme": "xyz",
+            "tiles": [
+                "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png",
+                "https://b.tile.openstreetmap.org/{z}/{x}/{y}.png",
+                "https://c.tile.openstreetmap.org/{z}/{x}/{y}.png"
+            ],
+            "minzoom": 0,
+            "maxzoom": 18,
+            "bounds": [ -180, -85, 180, 85 ]
+        }"#;
+
+        let tilejson = osm_example();
+
+        assert_eq!(decode(encoded_str).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_encode_pretty() {
+        let tilejson = TileJson {
+            center: Some(Center { longitude: -0.1, latitude: 51.5, zoom: Some(10) }),
+            tile_size: Some(512),
+            ..osm_example()
+        };
+        let encoded = encode_pretty(&tilejson).unwrap();
+        assert!(encoded.starts_with("{\n  \"tilejson\": \"1.0.0\","));
+        assert_eq!(decode(&encoded).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_tile_size_round_trip() {
+        let encoded_str = r#"{"tilejson":"2.2.0","version":"1.0.0","scheme":"xyz","tiles":["https://example.com/{z}/{x}/{y}@2x.png"],"minzoom":0,"maxzoom":30,"tileSize":512}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.tile_size, Some(512));
+        assert_eq!(tilejson.tile_size(), 512);
+        assert_eq!(encode(&tilejson).unwrap(), encoded_str);
+        assert_eq!(TileJson::default().tile_size(), 256);
+    }
+
+    #[test]
+    fn test_decode_vector_layers() {
+        let encoded_str = r#"{
+            "tilejson": "3.0.0",
+            "tiles": ["https://tiles.example.com/{z}/{x}/{y}.pbf"],
+            "vector_layers": [
+                {
+                    "id": "water",
+                    "fields": {},
+                    "minzoom": 0,
+                    "maxzoom": 14
+                },
+                {
+                    "id": "roads",
+                    "description": "Roads and their classification",
+                    "fields": {
+                        "class": "One of: motorway, primary, secondary, street",
+                        "oneway": "Number, 1 for one-way roads"
+                    },
+                    "minzoom": 6
+                }
+            ]
+        }"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.vector_layers.len(), 2);
+
+        let water = &tilejson.vector_layers[0];
+        assert_eq!(water.id, "water");
+        assert!(water.fields.is_empty());
+        assert_eq!((water.minzoom, water.maxzoom), (Some(0), Some(14)));
+
+        let roads = &tilejson.vector_layers[1];
+        assert_eq!(roads.description.as_deref(), Some("Roads and their classification"));
+        assert_eq!(roads.fields["oneway"], "Number, 1 for one-way roads");
+        assert_eq!((roads.minzoom, roads.maxzoom), (Some(6), None));
+
+        let encoded = encode(&tilejson).unwrap();
+        assert!(encoded.contains(r#"{"id":"water","fields":{},"minzoom":0,"maxzoom":14}"#));
+        assert_eq!(decode(&encoded).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_custom_fields_round_trip() {
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":[],"format":"pbf","mtime":1589241872,"tilestats":{"layerCount":1}}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.format.as_deref(), Some("pbf"));
+        assert_eq!(tilejson.other["mtime"], json!(1_589_241_872));
+        assert_eq!(tilejson.other["tilestats"], json!({ "layerCount": 1 }));
+
+        let encoded = encode(&tilejson).unwrap();
+        assert!(encoded.contains(r#""format":"pbf""#));
+        assert!(encoded.contains(r#""mtime":1589241872"#));
+        assert!(encoded.contains(r#""tilestats":{"layerCount":1}"#));
+        assert_eq!(decode(&encoded).unwrap(), tilejson);
+
+        let tilejson = TileJson { format: Some("png".to_owned()), ..tilejson };
+        assert!(encode(&tilejson).unwrap().contains(r#""format":"png""#));
+
+        let tilejson = TileJson { format: None, ..tilejson };
+        assert!(!encode(&tilejson).unwrap().contains("format"));
+    }
+
+    #[test]
+    fn test_reader_writer_round_trip() {
+        let tilejson = TileJson { fillzoom: Some(12), ..osm_example() };
+        let mut buf = Vec::new();
+        tilejson.to_writer(&mut buf).unwrap();
+        assert_eq!(buf, encode(&tilejson).unwrap().into_bytes());
+        assert_eq!(TileJson::from_reader(buf.as_slice()).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_file_round_trip() {
+        let path = std::env::temp_dir().join(format!("tilejson-test-{}.json", std::process::id()));
+        let tilejson = osm_example();
+        tilejson.to_file(&path).unwrap();
+        let read = TileJson::from_file(&path);
+        fs::remove_file(&path).unwrap();
+        assert_eq!(read.unwrap(), tilejson);
+
+        assert!(matches!(TileJson::from_file(&path), Err(Error::Io(_))));
+    }
+
+    #[test]
+    fn test_from_str_and_try_from() {
+        let encoded = encode(&osm_example()).unwrap();
+        assert_eq!(encoded.parse::<TileJson>().unwrap(), osm_example());
+        assert_eq!(TileJson::try_from(encoded.as_str()).unwrap(), osm_example());
+        assert!(matches!("{ not json".parse::<TileJson>(), Err(Error::Json(_))));
+        assert!(matches!(TileJson::try_from("{ not json"), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn test_decode_zoom_strings() {
+        let number = decode(r#"{"tilejson":"2.2.0","tiles":[],"minzoom":0,"maxzoom":18,"fillzoom":16}"#).unwrap();
+        let string = decode(r#"{"tilejson":"2.2.0","tiles":[],"minzoom":"0","maxzoom":"18","fillzoom":"16"}"#).unwrap();
+        assert_eq!((number.minzoom, number.maxzoom, number.fillzoom), (0, 18, Some(16)));
+        assert_eq!(string, number);
+        assert!(encode(&string).unwrap().contains(r#""maxzoom":18,"#));
+
+        assert_eq!(decode(r#"{"tilejson":"2.2.0","tiles":[],"fillzoom":null}"#).unwrap().fillzoom, None);
+        assert!(decode(r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":"eighteen"}"#).is_err());
+        assert!(decode(r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":300}"#).is_err());
+        assert!(decode(r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":-1}"#).is_err());
+    }
+
+    #[test]
+    fn test_coordinates_keep_f64_precision() {
+        let tilejson = TileJson {
+            bounds: Some(Bounds::from([-122.41941550000001, 37.77492950000001, -122.3, 37.8])),
+            center: Some(Center { longitude: -122.41941550000001, latitude: 37.77492950000001, zoom: Some(12) }),
+            ..TileJson::default()
+        };
+        let encoded = encode(&tilejson).unwrap();
+        assert!(encoded.contains("-122.41941550000001"));
+        let decoded = decode(&encoded).unwrap();
+        assert_eq!(decoded.bounds.unwrap().left, -122.41941550000001);
+        assert_eq!(decoded.center.unwrap().longitude, -122.41941550000001);
+        assert_eq!(decoded, tilejson);
+    }
+
+    #[test]
+    fn test_value_conversions() {
+        let value = Value::from(osm_example());
+        assert_eq!(value["name"], json!("OpenStreetMap"));
+        assert_eq!(value["bounds"], json!([-180.0, -85.0, 180.0, 85.0]));
+        assert_eq!(TileJson::try_from(&value).unwrap(), osm_example());
+
+        let style = json!({ "version": 8, "sources": { "osm": value } });
+        assert_eq!(TileJson::try_from(&style["sources"]["osm"]).unwrap(), osm_example());
+        assert!(matches!(TileJson::try_from(&style), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn test_decode_invalid() {
+        assert!(matches!(decode("{ not json"), Err(Error::Json(_))));
+        assert!(matches!(decode(r#"{"tilejson": "2.2.0"}"#), Err(Error::Json(_))));
+        let three_bounds = r#"{"tilejson": "2.2.0", "tiles": [], "bounds": [-180, -85, 180]}"#;
+        assert!(matches!(decode(three_bounds), Err(Error::Json(_))));
+        let one_center = r#"{"tilejson": "2.2.0", "tiles": [], "center": [-122.4]}"#;
+        assert!(matches!(decode(one_center), Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn test_decode_reader() {
+        let encoded = encode(&osm_example()).unwrap();
+        assert_eq!(decode_reader(encoded.as_bytes()).unwrap(), osm_example());
+        assert!(matches!(decode_reader(&b"{ not json"[..]), Err(Error::Json(_))));
+    }
+
+    #[cfg(feature = "tokio")]
+    #[test]
+    fn test_decode_async_reader() {
+        let runtime = tokio::runtime::Builder::new_current_thread().build().unwrap();
+        let encoded = encode(&osm_example()).unwrap();
+
+        let decoded = runtime.block_on(decode_async_reader(encoded.as_bytes()));
+        assert_eq!(decoded.unwrap(), osm_example());
+
+        let decoded = runtime.block_on(decode_async_reader(&b"{ not json"[..]));
+        assert!(matches!(decoded, Err(Error::Json(_))));
+    }
+
+    #[test]
+    fn test_center_integer_zoom() {
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":[],"center":[-122.4,37.8,14.0]}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.center, Some(Center { longitude: -122.4, latitude: 37.8, zoom: Some(14) }));
+        assert!(encode(&tilejson).unwrap().contains(r#""center":[-122.4,37.8,14]"#));
+        assert_eq!(decode(&encode(&tilejson).unwrap()).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_center_fractional_zoom() {
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":[],"center":[-122.4,37.8,14.5]}"#;
+        let tilejson = decode_reader(encoded_str.as_bytes()).unwrap();
+        assert_eq!(tilejson.center.and_then(|center| center.zoom), Some(15));
+
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":[],"center":[-122.4,37.8,40.0]}"#;
+        let err = decode_reader(encoded_str.as_bytes()).unwrap_err();
+        assert!(err.to_string().contains("center zoom must lie in 0..=30"));
+    }
+
+    fn with_extra(key: &str, value: Value) -> TileJson {
+        let mut tilejson = TileJson::default();
+        tilejson.other.insert(key.to_owned(), value);
+        tilejson
+    }
+
+    #[test]
+    fn test_to_patch() {
+        let base = osm_example();
+        let mut tilejson = base.clone();
+        tilejson.maxzoom = 14;
+        tilejson.attribution = Some("(c) Example".to_owned());
+        tilejson.tiles.truncate(1);
+        tilejson.other.insert("mtime".to_owned(), Value::from(1_234));
+
+        let patch = tilejson.to_patch(&base);
+        assert_eq!(
+            patch,
+            json!({
+                "maxzoom": 14,
+                "attribution": "(c) Example",
+                "tiles": ["https://a.tile.openstreetmap.org/{z}/{x}/{y}.png"],
+                "mtime": 1_234,
+            })
+        );
+        assert_eq!(base.to_patch(&base), json!({}));
+
+        let mut overlay = serde_json::to_value(TileJson::default()).unwrap();
+        for (key, value) in patch.as_object().unwrap() {
+            overlay[key] = value.clone();
+        }
+        let mut patched = base.clone();
+        patched.merge(&serde_json::from_value(overlay).unwrap());
+        assert_eq!(patched, tilejson);
+    }
+
+    #[test]
+    fn test_merge_extras_prefer_other() {
+        let mut tilejson = with_extra("mtime", Value::from(1));
+        tilejson.merge(&with_extra("mtime", Value::from(2)));
+        assert_eq!(tilejson.other["mtime"], Value::from(2));
+    }
+
+    #[test]
+    fn test_merge_extras_prefer_self() {
+        let mut tilejson = with_extra("mtime", Value::from(1));
+        let policy = MergePolicy { extras: ExtrasPolicy::PreferSelf };
+        let mut overlay = with_extra("mtime", Value::from(2));
+        overlay.other.insert("owner".to_owned(), Value::from("ops"));
+        assert_eq!(tilejson.merge_with(&overlay, &policy), Ok(()));
+        assert_eq!(tilejson.other["mtime"], Value::from(1));
+        assert_eq!(tilejson.other["owner"], Value::from("ops"));
+    }
+
+    #[test]
+    fn test_merge_extras_error() {
+        let mut tilejson = with_extra("mtime", Value::from(1));
+        let policy = MergePolicy { extras: ExtrasPolicy::Error };
+        let overlay = TileJson { maxzoom: 14, ..with_extra("mtime", Value::from(2)) };
+        assert_eq!(
+            tilejson.merge_with(&overlay, &policy),
+            Err(ValidationError::ConflictingExtra { key: "mtime".to_owned() })
+        );
+        assert_eq!(tilejson, with_extra("mtime", Value::from(1)));
+
+        assert_eq!(tilejson.merge_with(&with_extra("mtime", Value::from(1)), &policy), Ok(()));
+    }
+
+    #[test]
+    fn test_to_tileserver_config() {
+        let config = osm_example().to_tileserver_config("osm");
+        let entry = &config["data"]["osm"];
+        assert_eq!(entry["mbtiles"], "osm.mbtiles");
+        assert_eq!(entry["tilejson"]["format"], "png");
+        assert_eq!(entry["tilejson"]["name"], "OpenStreetMap");
+        assert_eq!(entry["tilejson"]["minzoom"], 0);
+        assert_eq!(entry["tilejson"]["maxzoom"], 18);
+        assert_eq!(entry["tilejson"]["bounds"], json!([-180.0, -85.0, 180.0, 85.0]));
+        assert!(entry["tilejson"].get("center").is_none());
+
+        let vector = TileJson { tiles: vec!["https://x/{z}/{x}/{y}".to_owned()], ..TileJson::default() };
+        assert_eq!(vector.to_tileserver_config("v")["data"]["v"]["tilejson"]["format"], "pbf");
+    }
+
+    #[test]
+    fn test_to_cesium_imagery() {
+        let mut tilejson = osm_example();
+        let options = tilejson.to_cesium_imagery();
+        assert_eq!(options["url"], "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png");
+        assert_eq!(options["minimumLevel"], 0);
+        assert_eq!(options["maximumLevel"], 18);
+        assert_eq!(options["rectangle"]["west"], -std::f64::consts::PI);
+        assert_eq!(options["rectangle"]["north"], 85f64.to_radians());
+        assert_eq!(options["tileWidth"], 256);
+        assert_eq!(options["credit"], "(c) OpenStreetMap contributors, CC-BY-SA");
+
+        tilejson.scheme = Scheme::TMS;
+        tilejson.tile_size = Some(512);
+        let options = tilejson.to_cesium_imagery();
+        assert_eq!(options["url"], "https://a.tile.openstreetmap.org/{z}/{x}/{reverseY}.png");
+        assert_eq!(options["tileHeight"], 512);
+
+        tilejson.tiles = vec!["https://tms.example.com/{Z}/{X}/{-Y}.png".to_owned()];
+        assert_eq!(tilejson.to_cesium_imagery()["url"], "https://tms.example.com/{z}/{x}/{y}.png");
+
+        tilejson.scheme = Scheme::XYZ;
+        assert_eq!(tilejson.to_cesium_imagery()["url"], "https://tms.example.com/{z}/{x}/{reverseY}.png");
+    }
+
+    #[test]
+    fn test_generate_id() {
+        let tilejson = osm_example();
+        let id = tilejson.generate_id();
+        assert!(id.starts_with("openstreetmap-"));
+        assert_eq!(id.len(), "openstreetmap-".len() + 12);
+        assert_eq!(id, tilejson.clone().generate_id());
+
+        let mut reordered = tilejson.clone();
+        reordered.tiles.reverse();
+        reordered.tiles.push(reordered.tiles[0].clone());
+        assert_eq!(reordered.generate_id(), id);
+
+        let other = TileJson { maxzoom: 14, ..tilejson };
+        assert_ne!(other.generate_id(), id);
+
+        let unnamed = TileJson { name: Some(" -- ".to_owned()), ..TileJson::default() };
+        assert!(unnamed.generate_id().starts_with("tileset-"));
+        assert_eq!(slugify("My Tiles: v2 (beta)"), "my-tiles-v2-beta");
+    }
+
+    #[test]
+    fn test_fillzoom() {
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":["https://example.com/{z}/{x}/{y}.png"],"minzoom":0,"maxzoom":16,"fillzoom":14}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.fillzoom, Some(14));
+        assert_eq!(tilejson.validate(), Ok(()));
+        assert!(encode(&tilejson).unwrap().contains(r#""maxzoom":16,"fillzoom":14"#));
+        assert_eq!(decode(&encode(&tilejson).unwrap()).unwrap(), tilejson);
+        assert_eq!(TileJson::default().fillzoom, None);
+
+        let tilejson = TileJson { maxzoom: 12, ..tilejson };
+        assert_eq!(
+            tilejson.validate(),
+            Err(ValidationError::FillzoomOutOfRange { fillzoom: 14, minzoom: 0, maxzoom: 12 })
+        );
+    }
+
+    #[test]
+    fn test_decode_lenient() {
+        let encoded_str = r#"{"tilejson":"2.2.0","tiles":[],"minzoom":18,"maxzoom":0}"#;
+        let tilejson = decode_lenient(encoded_str).unwrap();
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 18));
+        assert_eq!((decode(encoded_str).unwrap().minzoom, decode(encoded_str).unwrap().maxzoom), (18, 0));
+        let as_given = DecodeOptions { normalize_zoom_range: false, ..DecodeOptions::lenient() };
+        let tilejson = decode_with(encoded_str, &as_given).unwrap();
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (18, 0));
+        assert!(decode_lenient("{ not json").is_err());
+    }
+
+    #[test]
+    fn test_decode_with() {
+        let encoded_str = r#"{"tilejson":"2.2.0","name":"no tiles yet"}"#;
+        assert!(decode_with(encoded_str, &DecodeOptions::strict()).is_err());
+        assert!(decode(encoded_str).is_err());
+        let tilejson = decode_with(encoded_str, &DecodeOptions::lenient()).unwrap();
+        assert!(tilejson.tiles.is_empty());
+        assert_eq!(tilejson.name(), Some("no tiles yet"));
+
+        let unversioned = r#"{"tiles":[]}"#;
+        assert!(decode_with(unversioned, &DecodeOptions { require_tiles: false, ..DecodeOptions::strict() }).is_err());
+        let options = DecodeOptions { require_tilejson_version: false, ..DecodeOptions::strict() };
+        assert_eq!(decode_with(unversioned, &options).unwrap().tilejson, "2.2.0");
+
+        let strings = r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":"18","center":[0,"0",2]}"#;
+        assert_eq!(decode_with(strings, &DecodeOptions::default()).unwrap().maxzoom, 18);
+        assert_eq!(decode(strings).unwrap().maxzoom, 18);
+        let string_zoom = r#"{"tilejson":"2.2.0","tiles":[],"minzoom":"5"}"#;
+        assert!(matches!(decode_with(string_zoom, &DecodeOptions::strict()), Err(Error::Json(_))));
+        let exact = DecodeOptions { coerce_string_numbers: false, ..DecodeOptions::lenient() };
+        assert!(decode_with(strings, &exact).is_err());
+        assert!(decode_with(r#"{"tiles":[],"center":[0,"0",2]}"#, &exact).is_err());
+        assert_eq!(decode_with(r#"{"tiles":[],"maxzoom":18}"#, &exact).unwrap().maxzoom, 18);
+    }
+
+    #[test]
+    fn test_from_mbtiles_metadata() {
+        let rows = [
+            ("name", "OpenMapTiles"),
+            ("format", "pbf"),
+            ("bounds", "-180.0,-85.0511,180.0,85.0511"),
+            ("center", "-12.2168, 28.6135, 4"),
+            ("minzoom", "0"),
+            ("maxzoom", "14"),
+            ("attribution", "<a href=\"https://openmaptiles.org/\">&copy; OpenMapTiles</a>"),
+            ("description", "A tileset showcasing all layers in OpenMapTiles."),
+            ("type", "baselayer"),
+            ("version", "3.15.0"),
+            (
+                "json",
+                r#"{"vector_layers":[{"id":"water","fields":{"class":"String"},"minzoom":0,"maxzoom":14}],"tilestats":{"layerCount":1}}"#,
+            ),
+        ];
+        let rows: Vec<(String, String)> = rows.iter().map(|(k, v)| (k.to_string(), v.to_string())).collect();
+        let tilejson = from_mbtiles_metadata(&rows).unwrap();
+
+        assert_eq!(tilejson.tilejson, "2.2.0");
+        assert_eq!(tilejson.name(), Some("OpenMapTiles"));
+        assert_eq!(tilejson.version(), "3.15.0");
+        assert_eq!(tilejson.format(), "pbf");
+        assert_eq!((tilejson.minzoom, tilejson.maxzoom), (0, 14));
+        assert_eq!(tilejson.bounds, Some(Bounds::from([-180.0, -85.0511, 180.0, 85.0511])));
+        assert_eq!(tilejson.center, Some(Center { longitude: -12.2168, latitude: 28.6135, zoom: Some(4) }));
+        assert_eq!(tilejson.vector_layers.len(), 1);
+        assert_eq!(tilejson.vector_layers[0].id, "water");
+        assert_eq!(tilejson.other["type"], "baselayer");
+        assert!(!tilejson.other.contains_key("tilestats"));
+        assert!(tilejson.tiles.is_empty());
+
+        let rows = vec![("tileSize".to_owned(), "512".to_owned()), ("fillzoom".to_owned(), "12".to_owned())];
+        let tilejson = from_mbtiles_metadata(&rows).unwrap();
+        assert_eq!((tilejson.tile_size, tilejson.fillzoom), (Some(512), Some(12)));
+        assert!(tilejson.other.is_empty());
+
+        let broken = vec![("maxzoom".to_owned(), "fourteen".to_owned())];
+        assert!(from_mbtiles_metadata(&broken).is_err());
+        let broken = vec![("tileSize".to_owned(), "large".to_owned())];
+        assert!(from_mbtiles_metadata(&broken).is_err());
+        let broken = vec![("json".to_owned(), "{".to_owned())];
+        assert!(from_mbtiles_metadata(&broken).is_err());
+    }
+
+    #[test]
+    fn test_to_mbtiles_metadata() {
+        let rows = osm_example().to_mbtiles_metadata();
+        let keys: Vec<&str> = rows.iter().map(|(key, _)| key.as_str()).collect();
+        assert_eq!(keys, vec!["name", "format", "bounds", "minzoom", "maxzoom", "attribution", "description", "version"]);
+        assert_eq!(rows[1].1, "png");
+        assert_eq!(rows[2].1, "-180,-85,180,85");
+        let rows = TileJson::new(vec!["https://a/{z}/{x}/{y}.webp".to_owned()]).to_mbtiles_metadata();
+        assert!(rows.contains(&("format".to_owned(), "webp".to_owned())));
+
+        let tilejson = TileJson {
+            center: Some(Center { longitude: 8.5, latitude: 47.3, zoom: Some(10) }),
+            vector_layers: vec![VectorLayer {
+                id: "water".to_owned(),
+                fields: vec![("class".to_owned(), "String".to_owned())].into_iter().collect(),
+                description: None,
+                minzoom: Some(0),
+                maxzoom: Some(14),
+            }],
+            ..osm_example()
+        };
+        let rows = tilejson.to_mbtiles_metadata();
+        assert!(rows.contains(&("center".to_owned(), "8.5,47.3,10".to_owned())));
+        assert!(rows.iter().any(|(key, value)| key == "json" && value.contains(r#""id":"water""#)));
+
+        let imported = from_mbtiles_metadata(&rows).unwrap();
+        assert_eq!(imported.to_mbtiles_metadata(), rows);
+        assert_eq!(imported.vector_layers, tilejson.vector_layers);
+        assert_eq!(imported.center, tilejson.center);
+
+        let rows = TileJson::default().to_mbtiles_metadata();
+        assert!(rows.iter().all(|(key, _)| !["name", "format", "bounds", "center", "json"].contains(&key.as_str())));
+
+        let rows = vec![("type".to_owned(), "baselayer".to_owned())];
+        let mut imported = from_mbtiles_metadata(&rows).unwrap();
+        imported.other.insert("mtime".to_owned(), json!(1_589_241_872));
+        let rows = imported.to_mbtiles_metadata();
+        assert!(rows.contains(&("type".to_owned(), "baselayer".to_owned())));
+        assert!(rows.iter().all(|(key, _)| key != "mtime"));
+    }
+
+    #[test]
+    fn test_decode_bytes() {
+        let encoded = encode(&osm_example()).unwrap();
+        assert_eq!(decode_bytes(encoded.as_bytes()).unwrap(), osm_example());
+        for end in 0..encoded.len() {
+            assert!(decode_bytes(&encoded.as_bytes()[..end]).is_err(), "{}", &encoded[..end]);
+        }
+        assert!(decode_bytes(b"").is_err());
+        assert!(decode_bytes(b"{\"tilejson\":\"2.2.0\",\"tiles\":[],\"name\":\"\xff\xfe\"}").is_err());
+        assert!(decode_bytes(&[0xc3, 0x28, 0xa0, 0xa1]).is_err());
+
+        let adversarial = [
+            r#"{"tilejson":"2.2.0","tiles":[],"bounds":[1,2,3]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"bounds":[1,2,3,4,5]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"bounds":["1e400",0,1,1]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"bounds":{"left":0}}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"center":[]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"center":[0,0,1e300]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"center":[0,0,"-1e300"]}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"maxzoom":"99999999999999999999"}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"minzoom":1.5}"#,
+            r#"{"tilejson":"2.2.0","tiles":[],"grids":[[]]}"#,
+        ];
+        for json in adversarial.iter() {
+            assert!(decode_bytes(json.as_bytes()).is_err(), "{}", json);
+            assert!(decode_lenient(json).is_err(), "{}", json);
+        }
+        let nested = format!("{{\"tilejson\":\"2.2.0\",\"tiles\":[],\"x\":{}{}}}", "[".repeat(10_000), "]".repeat(10_000));
+        assert!(decode_bytes(nested.as_bytes()).is_err());
+        assert!(decode_lenient(&nested).is_err());
+    }
+
+    #[test]
+    fn test_to_gl_source() {
+        let source = osm_example().to_gl_source();
+        assert_eq!(source["type"], "raster");
+        assert_eq!(source["tiles"].as_array().map(Vec::len), Some(3));
+        assert_eq!(source["tiles"][0], "https://a.tile.openstreetmap.org/{z}/{x}/{y}.png");
+        assert_eq!((source["minzoom"].as_u64(), source["maxzoom"].as_u64()), (Some(0), Some(18)));
+        assert_eq!(source["bounds"], json!([-180.0, -85.0, 180.0, 85.0]));
+        assert_eq!(source["tileSize"], 256);
+        assert_eq!(source["attribution"], "(c) OpenStreetMap contributors, CC-BY-SA");
+        assert!(source.get("scheme").is_none());
+
+        let vector = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{y}.pbf".to_owned()],
+            scheme: Scheme::TMS,
+            ..TileJson::default()
+        };
+        let source = vector.to_gl_source();
+        assert_eq!(source["type"], "vector");
+        assert_eq!(source["scheme"], "tms");
+        assert!(source.get("tileSize").is_none());
+        assert!(source.get("attribution").is_none());
+    }
+
+    #[test]
+    fn test_decode_many() {
+        let encoded_str = r#"[
+            {"tilejson":"2.2.0","name":"streets","tiles":["https://example.com/streets/{z}/{x}/{y}.pbf"]},
+            {"tilejson":"3.0.0","name":"satellite","tiles":["https://example.com/satellite/{z}/{x}/{y}.jpg"]}
+        ]"#;
+        let tilejsons = decode_many(encoded_str).unwrap();
+        let names: Vec<_> = tilejsons.iter().map(TileJson::name).collect();
+        assert_eq!(names, vec![Some("streets"), Some("satellite")]);
+
+        assert_eq!(decode_many("[]").unwrap(), vec![]);
+        assert!(decode_many(r#"[{"tilejson":"2.2.0","tiles":[]},{"tilejson":"2.2.0"}]"#).is_err());
+        assert!(decode_many(r#"{"tilejson":"2.2.0","tiles":[]}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_stream() {
+        let encoded_str = r#"[
+            {"tilejson":"2.2.0","name":"streets","tiles":["https://example.com/streets/{z}/{x}/{y}.pbf"]},
+            {"tilejson":"3.0.0","name":"satellite","tiles":["https://example.com/satellite/{z}/{x}/{y}.jpg"]}
+        ]"#;
+        let names: Vec<_> = decode_stream(encoded_str.as_bytes()).map(|tilejson| tilejson.unwrap().name).collect();
+        assert_eq!(names, vec![Some("streets".to_owned()), Some("satellite".to_owned())]);
+
+        assert_eq!(decode_stream(" [ ] ".as_bytes()).count(), 0);
+        assert!(matches!(decode_stream("".as_bytes()).next(), Some(Err(Error::Json(_)))));
+        assert!(matches!(decode_stream(r#"{"tilejson":"2.2.0","tiles":[]}"#.as_bytes()).next(), Some(Err(_))));
+    }
+
+    #[test]
+    fn test_decode_stream_malformed_element() {
+        let encoded_str = r#"[
+            {"tilejson":"2.2.0","name":"streets","tiles":[]},
+            {"tilejson":"2.2.0","name":"broken","maxzoom":"high"},
+            {"tilejson":"2.2.0","name":"unreached","tiles":[]}
+        ]"#;
+        let mut stream = decode_stream(encoded_str.as_bytes());
+        assert_eq!(stream.next().unwrap().unwrap().name(), Some("streets"));
+        assert!(matches!(stream.next(), Some(Err(Error::Json(_)))));
+        assert!(stream.next().is_none());
+
+        let mut stream = decode_stream(r#"[{"tilejson":"2.2.0","tiles":[]} {"tilejson":"2.2.0","tiles":[]}]"#.as_bytes());
+        assert!(stream.next().unwrap().is_ok());
+        assert!(matches!(stream.next(), Some(Err(Error::Json(_)))));
+        assert!(stream.next().is_none());
+    }
+
+    #[test]
+    fn test_string_or_vec_endpoints() {
+        let bare = decode(r#"{"tilejson":"2.2.0","tiles":[],"grids":"g.json","data":"d.geojson"}"#).unwrap();
+        let array = decode(r#"{"tilejson":"2.2.0","tiles":[],"grids":["g.json"],"data":["d.geojson"]}"#).unwrap();
+        assert_eq!(bare.grids, vec!["g.json"]);
+        assert_eq!(bare.data, vec!["d.geojson"]);
+        assert_eq!(bare, array);
+        assert!(encode(&bare).unwrap().contains(r#""grids":["g.json"],"data":["d.geojson"]"#));
+
+        assert!(decode(r#"{"tilejson":"2.2.0","tiles":[],"grids":7}"#).is_err());
+    }
+
+    #[test]
+    fn test_decode_lenient_missing_tilejson() {
+        let encoded_str = r#"{"name":"legacy","tiles":["https://example.com/{z}/{x}/{y}.png"]}"#;
+        let tilejson = decode_lenient(encoded_str).unwrap();
+        assert_eq!(tilejson.tilejson, "2.2.0");
+        assert_eq!(tilejson.name(), Some("legacy"));
+        assert!(decode(encoded_str).is_err());
+
+        let encoded_str = r#"{"tilejson":"3.0.0","tiles":[]}"#;
+        assert_eq!(decode_lenient(encoded_str).unwrap().tilejson, "3.0.0");
+        assert!(decode_lenient(r#"{"tiles":"none"}"#).is_err());
+    }
+
+    #[test]
+    fn test_id_round_trip() {
+        let encoded_str = r#"{"tilejson":"2.2.0","id":"mapbox.satellite","tiles":[]}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.id, Some("mapbox.satellite".to_owned()));
+        assert!(tilejson.other.is_empty());
+        assert!(encode(&tilejson).unwrap().contains(r#""id":"mapbox.satellite""#));
+        assert_eq!(decode(&encode(&tilejson).unwrap()).unwrap(), tilejson);
+    }
+
+    #[test]
+    fn test_ensure_id() {
+        let mut tilejson = osm_example();
+        let generated = tilejson.generate_id();
+        tilejson.ensure_id();
+        assert_eq!(tilejson.id, Some(generated.clone()));
+        assert_eq!(tilejson.generate_id(), generated);
+
+        tilejson.id = Some("custom".to_owned());
+        tilejson.ensure_id();
+        assert_eq!(tilejson.id, Some("custom".to_owned()));
+    }
+
+    #[test]
+    fn test_scheme_serde() {
+        assert_eq!(serde_json::to_value(Scheme::TMS).unwrap(), json!(Scheme::TMS.to_string()));
+        assert_eq!(serde_json::from_value::<Scheme>(json!("xyz")).unwrap(), Scheme::XYZ);
+    }
+
+    #[test]
+    fn test_scheme_case_insensitive_and_other() {
+        assert_eq!(serde_json::from_value::<Scheme>(json!("XYZ")).unwrap(), Scheme::XYZ);
+        assert_eq!(serde_json::from_value::<Scheme>(json!("tms")).unwrap(), Scheme::TMS);
+        assert_eq!(serde_json::from_value::<Scheme>(json!("custom")).unwrap(), Scheme::Other("custom".to_owned()));
+        assert!(serde_json::from_value::<Scheme>(json!(1)).is_err());
+
+        let encoded_str = r#"{"tilejson":"2.2.0","scheme":"custom","tiles":["https://example.com/{z}/{x}/{y}.png"]}"#;
+        let tilejson = decode(encoded_str).unwrap();
+        assert_eq!(tilejson.scheme, Scheme::Other("custom".to_owned()));
+        assert!(encode(&tilejson).unwrap().contains(r#""scheme":"custom""#));
+        assert_eq!(tilejson.tile_url(1, 0, 0), Some("https://example.com/1/0/0.png".to_owned()));
+        assert_eq!(decode(r#"{"tilejson":"2.2.0","scheme":"TMS","tiles":[]}"#).unwrap().scheme, Scheme::TMS);
+    }
+}
@@ -0,0 +1,40 @@
+//! HTML sanitizing of the fields clients may render as HTML, behind the
+//! `html-sanitize` feature.
+
+use super::TileJson;
+
+impl TileJson {
+    /// Strips the tags and attributes that could serve XSS or beacon
+    /// tracking, such as `<script>` or `onclick`, from `attribution`,
+    /// `legend` and `template` in place, keeping their text and safe markup
+    /// like links.
+    pub fn sanitize_html_fields(&mut self) {
+        let fields = self.attribution.iter_mut().chain(&mut self.legend).chain(&mut self.template);
+        for html in fields {
+            *html = ammonia::clean(html);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tilejson::tests::osm_example;
+
+    #[test]
+    fn test_sanitize_html_fields() {
+        let mut tilejson = TileJson {
+            attribution: Some(r#"<a href="x" onclick="evil()">OSM</a>"#.to_owned()),
+            legend: Some("<b>Roads</b><script>track()</script>".to_owned()),
+            template: None,
+            ..osm_example()
+        };
+        tilejson.sanitize_html_fields();
+
+        let attribution = tilejson.attribution.unwrap();
+        assert!(!attribution.contains("onclick"), "{}", attribution);
+        assert!(attribution.contains(r#"href="x""#) && attribution.contains(">OSM</a>"), "{}", attribution);
+        assert_eq!(tilejson.legend, Some("<b>Roads</b>".to_owned()));
+        assert_eq!(tilejson.template, None);
+    }
+}
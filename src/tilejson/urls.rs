@@ -0,0 +1,97 @@
+//! Syntax checks of the endpoints of `TileJson`, behind the `url-validate`
+//! feature.
+
+use super::TileJson;
+use crate::error::ValidationError;
+use crate::url_template::{Segment, UrlTemplate};
+
+use url::Url;
+
+impl TileJson {
+    /// Checks that every endpoint of `tiles`, `grids` and `data` is an
+    /// absolute URL once its placeholders are filled in with `0`.
+    ///
+    /// Fails with a `ValidationError::InvalidUrl` for each endpoint that
+    /// doesn't parse.
+    pub fn validate_urls(&self) -> Result<(), Vec<ValidationError>> {
+        self.check_urls(None)
+    }
+
+    /// Like `validate_urls`, but resolves relative endpoints such as
+    /// `/tiles/{z}/{x}/{y}.png` against `base` instead of rejecting them.
+    pub fn validate_urls_with_base(&self, base: &Url) -> Result<(), Vec<ValidationError>> {
+        self.check_urls(Some(base))
+    }
+
+    /// Parses every endpoint, against `base` if given, collecting failures.
+    fn check_urls(&self, base: Option<&Url>) -> Result<(), Vec<ValidationError>> {
+        let errors: Vec<ValidationError> = self
+            .tiles
+            .iter()
+            .chain(&self.grids)
+            .chain(&self.data)
+            .filter_map(|url| {
+                let filled = filled_placeholders(url);
+                let parsed = match base {
+                    Some(base) => base.join(&filled),
+                    None => Url::parse(&filled),
+                };
+                parsed.err().map(|error| ValidationError::InvalidUrl { url: url.clone(), error })
+            })
+            .collect();
+        if errors.is_empty() {
+            Ok(())
+        } else {
+            Err(errors)
+        }
+    }
+}
+
+/// Replaces every placeholder of `url` with `0`, leaving any other braces
+/// as they are.
+fn filled_placeholders(url: &str) -> String {
+    let mut filled = String::new();
+    for segment in UrlTemplate::parse(url).segments() {
+        match segment {
+            Segment::Literal(text) => filled.push_str(text),
+            _ => filled.push('0'),
+        }
+    }
+    filled
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::tilejson::tests::osm_example;
+
+    #[test]
+    fn test_validate_urls() {
+        assert_eq!(osm_example().validate_urls(), Ok(()));
+
+        let mut tilejson = osm_example();
+        tilejson.tiles[1] = "https://[::1/{z}/{x}/{y}.png".to_owned();
+        tilejson.data = vec!["https://tiles.example.com/data.geojson".to_owned()];
+        let errors = tilejson.validate_urls().unwrap_err();
+        assert_eq!(errors.len(), 1);
+        assert!(matches!(
+            &errors[0],
+            ValidationError::InvalidUrl { url, error: url::ParseError::InvalidIpv6Address } if *url == tilejson.tiles[1]
+        ));
+
+        let quadkey = TileJson::new(vec!["https://ecn.t0.tiles.virtualearth.net/tiles/a{quadkey}.jpeg?g=1".to_owned()]);
+        assert_eq!(quadkey.validate_urls(), Ok(()));
+    }
+
+    #[test]
+    fn test_validate_urls_with_base() {
+        let tilejson = TileJson::new(vec!["/tiles/{z}/{x}/{y}.png".to_owned()]);
+        assert!(matches!(
+            tilejson.validate_urls().unwrap_err().as_slice(),
+            [ValidationError::InvalidUrl { error: url::ParseError::RelativeUrlWithoutBase, .. }]
+        ));
+        let base = Url::parse("https://tiles.example.com/styles/").unwrap();
+        assert_eq!(tilejson.validate_urls_with_base(&base), Ok(()));
+        assert_eq!(osm_example().validate_urls_with_base(&base), Ok(()));
+    }
+}
@@ -0,0 +1,221 @@
+//! The WMTS `GetCapabilities` export of `TileJson`, behind the `wmts`
+//! feature.
+
+use super::{endpoint_extension, Scheme, TileJson};
+use crate::error::{Error, ValidationError};
+use crate::mercator;
+use crate::url_template::{Segment, UrlTemplate};
+
+use std::fmt::{self, Write};
+
+/// The identifier of the tile matrix set every layer is published in.
+const TILE_MATRIX_SET: &str = "WebMercatorQuad";
+
+/// The top left corner of the WebMercatorQuad tile matrices, in meters.
+const TOP_LEFT_CORNER: &str = "-20037508.3427892 20037508.3427892";
+
+impl TileJson {
+    /// Describes the tileset as a minimal WMTS 1.0.0 `Capabilities` document
+    /// with a single layer, identified by `name` (or `id`), covering
+    /// `bounds` in a WebMercatorQuad tile matrix set spanning `minzoom` to
+    /// `maxzoom`, and served from the first endpoint of `tiles`.
+    ///
+    /// Fails with `ValidationError::NoTiles` if there are no tile endpoints,
+    /// `ValidationError::InvalidBounds` if `bounds` is degenerate, and
+    /// `ValidationError::UnsupportedPlaceholder` if the endpoint needs a row
+    /// WMTS can't provide: `{-y}`, `{quadkey}`, or `{y}` under any scheme
+    /// but `Scheme::XYZ`.
+    pub fn to_wmts_capabilities(&self) -> Result<String, Error> {
+        let url = self.tiles.first().ok_or(ValidationError::NoTiles)?;
+        let extent = self
+            .bounds_extent()
+            .ok_or(ValidationError::InvalidBounds { bounds: self.bounds_or_default() })?;
+        let template = self.wmts_template(url)?;
+        let mut xml = String::new();
+        self.write_wmts_capabilities(&mut xml, url, extent, &template)
+            .expect("writing to a String never fails");
+        Ok(xml)
+    }
+
+    /// Writes the document described by `to_wmts_capabilities` for the
+    /// already checked endpoint, extent and `ResourceURL` template.
+    fn write_wmts_capabilities(
+        &self,
+        xml: &mut String,
+        url: &str,
+        [left, bottom, right, top]: [f64; 4],
+        template: &str,
+    ) -> fmt::Result {
+        let identifier = self.name.as_deref().or(self.id.as_deref()).unwrap_or("tileset");
+        let format = mime_type(endpoint_extension(url).unwrap_or(self.format()));
+        let tile_size = self.tile_size();
+
+        writeln!(xml, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+        writeln!(
+            xml,
+            concat!(
+                "<Capabilities xmlns=\"http://www.opengis.net/wmts/1.0\"",
+                " xmlns:ows=\"http://www.opengis.net/ows/1.1\"",
+                " xmlns:xlink=\"http://www.w3.org/1999/xlink\" version=\"1.0.0\">",
+            )
+        )?;
+        writeln!(xml, "  <Contents>")?;
+        writeln!(xml, "    <Layer>")?;
+        writeln!(xml, "      <ows:Title>{}</ows:Title>", escape(identifier))?;
+        if let Some(description) = &self.description {
+            writeln!(xml, "      <ows:Abstract>{}</ows:Abstract>", escape(description))?;
+        }
+        writeln!(xml, "      <ows:WGS84BoundingBox>")?;
+        writeln!(xml, "        <ows:LowerCorner>{} {}</ows:LowerCorner>", left, bottom)?;
+        writeln!(xml, "        <ows:UpperCorner>{} {}</ows:UpperCorner>", right, top)?;
+        writeln!(xml, "      </ows:WGS84BoundingBox>")?;
+        writeln!(xml, "      <ows:Identifier>{}</ows:Identifier>", escape(identifier))?;
+        writeln!(xml, "      <Style isDefault=\"true\"><ows:Identifier>default</ows:Identifier></Style>")?;
+        writeln!(xml, "      <Format>{}</Format>", format)?;
+        writeln!(
+            xml,
+            "      <TileMatrixSetLink><TileMatrixSet>{}</TileMatrixSet></TileMatrixSetLink>",
+            TILE_MATRIX_SET
+        )?;
+        writeln!(
+            xml,
+            "      <ResourceURL format=\"{}\" resourceType=\"tile\" template=\"{}\"/>",
+            format,
+            escape(template)
+        )?;
+        writeln!(xml, "    </Layer>")?;
+        writeln!(xml, "    <TileMatrixSet>")?;
+        writeln!(xml, "      <ows:Identifier>{}</ows:Identifier>", TILE_MATRIX_SET)?;
+        writeln!(xml, "      <ows:SupportedCRS>urn:ogc:def:crs:EPSG::3857</ows:SupportedCRS>")?;
+        let maxzoom = u32::from(self.maxzoom).min(mercator::MAX_ZOOM);
+        for z in u32::from(self.minzoom)..=maxzoom {
+            // Scale denominators are defined for 256 pixel tiles, larger tiles
+            // show the same area at a finer scale.
+            let scale_denominator = mercator::scale_denominator(z) * 256.0 / f64::from(tile_size);
+            let matrix_size = 1u64 << z;
+            writeln!(xml, "      <TileMatrix>")?;
+            writeln!(xml, "        <ows:Identifier>{}</ows:Identifier>", z)?;
+            writeln!(xml, "        <ScaleDenominator>{}</ScaleDenominator>", scale_denominator)?;
+            writeln!(xml, "        <TopLeftCorner>{}</TopLeftCorner>", TOP_LEFT_CORNER)?;
+            writeln!(xml, "        <TileWidth>{}</TileWidth>", tile_size)?;
+            writeln!(xml, "        <TileHeight>{}</TileHeight>", tile_size)?;
+            writeln!(xml, "        <MatrixWidth>{}</MatrixWidth>", matrix_size)?;
+            writeln!(xml, "        <MatrixHeight>{}</MatrixHeight>", matrix_size)?;
+            writeln!(xml, "      </TileMatrix>")?;
+        }
+        writeln!(xml, "    </TileMatrixSet>")?;
+        writeln!(xml, "  </Contents>")?;
+        writeln!(xml, "</Capabilities>")
+    }
+
+    /// Rewrites `url` into a WMTS `ResourceURL` template.
+    fn wmts_template(&self, url: &str) -> Result<String, ValidationError> {
+        let unsupported = |placeholder: &str| ValidationError::UnsupportedPlaceholder {
+            url: url.to_owned(),
+            placeholder: placeholder.to_owned(),
+        };
+        let mut template = String::new();
+        for segment in UrlTemplate::parse(url).segments() {
+            match segment {
+                Segment::Literal(text) => template.push_str(text),
+                Segment::Z => template.push_str("{TileMatrix}"),
+                Segment::X => template.push_str("{TileCol}"),
+                Segment::Y if self.scheme == Scheme::XYZ => template.push_str("{TileRow}"),
+                Segment::Y => return Err(unsupported("y")),
+                Segment::FlippedY => return Err(unsupported("-y")),
+                Segment::Quadkey => return Err(unsupported("quadkey")),
+            }
+        }
+        Ok(template)
+    }
+}
+
+/// The MIME type of a tile format.
+fn mime_type(format: &str) -> &'static str {
+    match format.to_ascii_lowercase().as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "webp" => "image/webp",
+        "pbf" | "mvt" => "application/vnd.mapbox-vector-tile",
+        _ => "application/octet-stream",
+    }
+}
+
+/// Escapes the characters that can't appear as is in XML text or attributes.
+fn escape(text: &str) -> String {
+    let mut escaped = String::with_capacity(text.len());
+    for c in text.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&apos;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::bounds::Bounds;
+    use crate::tilejson::tests::osm_example;
+
+    #[test]
+    fn test_to_wmts_capabilities() {
+        let tilejson = TileJson {
+            tiles: vec!["https://tiles.example.com/{z}/{x}/{y}.png?key=a&style=b".to_owned()],
+            minzoom: 2,
+            maxzoom: 5,
+            bounds: Some(Bounds::from([5.9, 45.8, 10.5, 47.8])),
+            ..osm_example()
+        };
+        let xml = tilejson.to_wmts_capabilities().unwrap();
+        let document = roxmltree::Document::parse(&xml).unwrap();
+
+        let text = |name: &str| -> Vec<&str> {
+            document
+                .descendants()
+                .filter(|node| node.has_tag_name(name))
+                .filter_map(|node| node.text())
+                .collect()
+        };
+        let matrices: Vec<&str> = document
+            .descendants()
+            .filter(|node| node.has_tag_name("TileMatrix"))
+            .filter_map(|node| node.children().find(|child| child.has_tag_name("Identifier")))
+            .filter_map(|node| node.text())
+            .collect();
+        assert_eq!(matrices, vec!["2", "3", "4", "5"]);
+        assert_eq!(text("MatrixWidth"), vec!["4", "8", "16", "32"]);
+        assert_eq!(text("Title"), vec!["OpenStreetMap"]);
+        assert_eq!(text("LowerCorner"), vec!["5.9 45.8"]);
+        assert_eq!(text("UpperCorner"), vec!["10.5 47.8"]);
+        assert_eq!(text("Format"), vec!["image/png"]);
+
+        let resource = document.descendants().find(|node| node.has_tag_name("ResourceURL")).unwrap();
+        assert_eq!(
+            resource.attribute("template"),
+            Some("https://tiles.example.com/{TileMatrix}/{TileCol}/{TileRow}.png?key=a&style=b")
+        );
+    }
+
+    #[test]
+    fn test_to_wmts_capabilities_errors() {
+        assert!(matches!(
+            TileJson::default().to_wmts_capabilities(),
+            Err(Error::Validation(ValidationError::NoTiles))
+        ));
+
+        let tms = TileJson { scheme: Scheme::TMS, ..osm_example() };
+        assert!(matches!(
+            tms.to_wmts_capabilities(),
+            Err(Error::Validation(ValidationError::UnsupportedPlaceholder { placeholder, .. })) if placeholder == "y"
+        ));
+
+        let flipped = TileJson { tiles: vec!["https://tiles.example.com/{z}/{x}/{-y}.png".to_owned()], ..osm_example() };
+        assert!(flipped.to_wmts_capabilities().is_err());
+    }
+}
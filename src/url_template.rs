@@ -0,0 +1,115 @@
+use crate::mercator;
+
+/// A tile endpoint split into literal text and `{z}`, `{x}`, `{y}`, `{-y}`
+/// and `{quadkey}` (or `{q}`) placeholders, so it can be expanded for many
+/// tiles without searching the endpoint again. Placeholders are recognized
+/// regardless of case, and any other braces are kept as they are.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) struct UrlTemplate {
+    segments: Vec<Segment>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Segment {
+    Literal(String),
+    Z,
+    X,
+    Y,
+    FlippedY,
+    Quadkey,
+}
+
+impl UrlTemplate {
+    pub(crate) fn parse(url: &str) -> UrlTemplate {
+        let mut segments = Vec::new();
+        let mut literal = String::new();
+        let mut rest = url;
+        while let Some(start) = rest.find('{') {
+            let end = match rest[start..].find('}') {
+                Some(end) => start + end,
+                None => break,
+            };
+            let name = &rest[start + 1..end];
+            let placeholder = match name.to_ascii_lowercase().as_str() {
+                "z" => Some(Segment::Z),
+                "x" => Some(Segment::X),
+                "y" => Some(Segment::Y),
+                "-y" => Some(Segment::FlippedY),
+                "quadkey" | "q" => Some(Segment::Quadkey),
+                _ => None,
+            };
+            match placeholder {
+                Some(placeholder) => {
+                    literal.push_str(&rest[..start]);
+                    if !literal.is_empty() {
+                        segments.push(Segment::Literal(std::mem::take(&mut literal)));
+                    }
+                    segments.push(placeholder);
+                }
+                None => literal.push_str(&rest[..=end]),
+            }
+            rest = &rest[end + 1..];
+        }
+        literal.push_str(rest);
+        if !literal.is_empty() {
+            segments.push(Segment::Literal(literal));
+        }
+        UrlTemplate { segments }
+    }
+
+    /// The literal text and placeholders of the endpoint, in order.
+    pub(crate) fn segments(&self) -> &[Segment] {
+        &self.segments
+    }
+
+    /// The URL of the XYZ tile `z`/`x`/`y`, with `scheme_y` substituted for
+    /// `{y}` as is, the TMS row for `{-y}` and the Bing quadkey for
+    /// `{quadkey}`, or `None` if there's a `{-y}` and `y` isn't a row at zoom
+    /// `z`.
+    pub(crate) fn expand(&self, z: u32, x: u32, y: u32, scheme_y: u32) -> Option<String> {
+        let mut url = String::new();
+        for segment in &self.segments {
+            match segment {
+                Segment::Literal(text) => url.push_str(text),
+                Segment::Z => url.push_str(&z.to_string()),
+                Segment::X => url.push_str(&x.to_string()),
+                Segment::Y => url.push_str(&scheme_y.to_string()),
+                Segment::FlippedY => url.push_str(&mercator::flip_y(z, y)?.to_string()),
+                Segment::Quadkey => url.push_str(&mercator::quadkey(z, x, y)),
+            }
+        }
+        Some(url)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_expand() {
+        let template = UrlTemplate::parse("https://tiles.example.com/{z}/{X}/{y}.png?key={KEY}");
+        assert_eq!(template.expand(3, 2, 1, 1).unwrap(), "https://tiles.example.com/3/2/1.png?key={KEY}");
+        assert_eq!(template.expand(10, 512, 340, 340).unwrap(), "https://tiles.example.com/10/512/340.png?key={KEY}");
+
+        assert_eq!(UrlTemplate::parse("{z}{x}{y}").expand(2, 1, 3, 3).unwrap(), "213");
+        assert_eq!(UrlTemplate::parse("{z}/{x}/{-y}/{y}").expand(2, 1, 3, 0).unwrap(), "2/1/0/0");
+        assert_eq!(UrlTemplate::parse("tiles/{z/{x}").expand(1, 1, 0, 0).unwrap(), "tiles/{z/{x}");
+        assert_eq!(UrlTemplate::parse("").expand(1, 1, 0, 0).unwrap(), "");
+    }
+
+    #[test]
+    fn test_expand_flipped_y_out_of_range() {
+        let template = UrlTemplate::parse("{z}/{x}/{-y}");
+        assert_eq!(template.expand(3, 0, 7, 7), Some("3/0/0".to_owned()));
+        assert_eq!(template.expand(3, 0, 8, 8), None);
+        assert_eq!(UrlTemplate::parse("{z}/{x}/{y}").expand(3, 0, 8, 8), Some("3/0/8".to_owned()));
+    }
+
+    #[test]
+    fn test_expand_quadkey() {
+        let template = UrlTemplate::parse("https://ecn.t0.tiles.virtualearth.net/tiles/a{quadkey}.jpeg?g=1");
+        assert_eq!(template.expand(3, 3, 5, 5).unwrap(), "https://ecn.t0.tiles.virtualearth.net/tiles/a213.jpeg?g=1");
+        assert_eq!(UrlTemplate::parse("{Q}/{z}").expand(3, 3, 5, 2).unwrap(), "213/3");
+    }
+}